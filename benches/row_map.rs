@@ -0,0 +1,93 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use thanes_egui_utils::{ChunkedRowMap, RowMap};
+
+/// Every other source index, like a filter which hides half of the items.
+fn rows(len: usize) -> Vec<usize> {
+    (0..len).map(|row| row * 2).collect()
+}
+
+fn build(c: &mut Criterion) {
+    let mut group = c.benchmark_group("row_map_build");
+    for len in [1_000, 100_000] {
+        group.bench_with_input(BenchmarkId::new("from_rows", len), &len, |b, len| {
+            b.iter(|| RowMap::from_rows(rows(*len)))
+        });
+        group.bench_with_input(BenchmarkId::new("push", len), &len, |b, len| {
+            b.iter(|| {
+                let mut map = RowMap::new();
+                for source_index in rows(*len) {
+                    map.push(source_index);
+                }
+                map
+            })
+        });
+    }
+    group.finish();
+}
+
+fn lookup(c: &mut Criterion) {
+    let mut group = c.benchmark_group("row_map_lookup");
+    for len in [1_000, 100_000] {
+        let rows = rows(len);
+        let map = RowMap::from_rows(rows.clone());
+        let source_index = (len - 1) * 2;
+        group.bench_with_input(BenchmarkId::new("row", len), &map, |b, map| {
+            b.iter(|| map.row(black_box(source_index)))
+        });
+        // What proxies did before RowMap.
+        group.bench_with_input(BenchmarkId::new("linear_scan", len), &rows, |b, rows| {
+            b.iter(|| rows.iter().position(|row| *row == black_box(source_index)))
+        });
+        group.bench_with_input(BenchmarkId::new("source_index", len), &map, |b, map| {
+            b.iter(|| map.source_index(black_box(len - 1)))
+        });
+    }
+    group.finish();
+}
+
+/// Pages of 100 items where a filter shows a varying number of each.
+fn chunk_lens(len: usize) -> Vec<usize> {
+    (0..len / 100).map(|chunk| chunk % 100).collect()
+}
+
+/// The rows of a 50 row viewport at every 1000th row, like a scroll area being dragged through the list.
+fn windows(total_rows: usize) -> impl Iterator<Item = std::ops::Range<usize>> {
+    (0..total_rows).step_by(1000).map(|start| start..start + 50)
+}
+
+fn scroll(c: &mut Criterion) {
+    let mut group = c.benchmark_group("chunked_row_map_scroll");
+    for len in [100_000, 1_000_000] {
+        let lens = chunk_lens(len);
+        let map = ChunkedRowMap::from_chunk_lens(lens.iter().copied());
+        group.bench_with_input(BenchmarkId::new("visible_chunks", len), &map, |b, map| {
+            b.iter(|| {
+                windows(map.len())
+                    .map(|window| map.visible_chunks(black_box(window)).count())
+                    .sum::<usize>()
+            })
+        });
+        // Summing chunk lengths up to the viewport, like proxies did before ChunkedRowMap.
+        group.bench_with_input(BenchmarkId::new("linear_scan", len), &lens, |b, lens| {
+            b.iter(|| {
+                windows(map.len())
+                    .map(|window| {
+                        let window = black_box(window);
+                        let mut start = 0;
+                        lens.iter()
+                            .filter(|len| {
+                                let shown = start < window.end && window.start < start + **len;
+                                start += **len;
+                                shown
+                            })
+                            .count()
+                    })
+                    .sum::<usize>()
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, build, lookup, scroll);
+criterion_main!(benches);
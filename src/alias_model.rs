@@ -0,0 +1,162 @@
+use std::collections::HashMap;
+use std::marker::PhantomData;
+
+use crate::{KeyedListModel, KeyedViewItem, ListModel, ModelError};
+
+/// Wraps a [KeyedListModel] so items can also be found by secondary keys,
+/// ex. IDs used by older versions of a file format or localized names.
+///
+/// [KeyedListModel::index_of] checks the item keys first, then the aliases.
+/// Aliases point at item keys, so they keep working when items are reordered.
+pub struct AliasModel<M, I> {
+    model: M,
+    aliases: HashMap<String, String>,
+    _item: PhantomData<I>,
+}
+
+impl<M, I> AliasModel<M, I>
+where
+    M: KeyedListModel<I>,
+    I: KeyedViewItem,
+{
+    pub fn new(model: M) -> Self {
+        Self {
+            model,
+            aliases: HashMap::new(),
+            _item: PhantomData,
+        }
+    }
+
+    pub fn inner(&self) -> &M {
+        &self.model
+    }
+
+    pub fn inner_mut(&mut self) -> &mut M {
+        &mut self.model
+    }
+
+    pub fn into_inner(self) -> M {
+        self.model
+    }
+
+    /// Resolve `alias` to the item with the key `key`. Replaces any existing target for the alias.
+    pub fn add_alias(&mut self, alias: impl Into<String>, key: impl Into<String>) {
+        self.aliases.insert(alias.into(), key.into());
+    }
+
+    pub fn remove_alias(&mut self, alias: &str) {
+        self.aliases.remove(alias);
+    }
+
+    pub fn clear_aliases(&mut self) {
+        self.aliases.clear();
+    }
+
+    /// Register the aliases each item reports, ex. a legacy ID field.
+    pub fn add_item_aliases(&mut self, aliases: impl Fn(&I) -> Vec<String>) {
+        for index in 0..self.model.len() {
+            if let Some(item) = self.model.item(index) {
+                let key = item.key().into_owned();
+                for alias in aliases(item) {
+                    self.aliases.insert(alias, key.clone());
+                }
+            }
+        }
+    }
+
+    /// Every alias of the item with the given key.
+    pub fn aliases_of(&self, key: &str) -> Vec<&str> {
+        self.aliases
+            .iter()
+            .filter(|(_, target)| target.as_str() == key)
+            .map(|(alias, _)| alias.as_str())
+            .collect()
+    }
+
+    /// The item key for a key or alias. Returns [None] if neither resolves to an item.
+    pub fn resolve(&self, key: &str) -> Option<String> {
+        self.index_of(key)
+            .and_then(|index| self.model.item(index))
+            .map(|item| item.key().into_owned())
+    }
+}
+
+impl<M, I> ListModel<I> for AliasModel<M, I>
+where
+    M: KeyedListModel<I>,
+    I: KeyedViewItem,
+{
+    fn is_empty(&self) -> bool {
+        self.model.is_empty()
+    }
+
+    fn len(&self) -> usize {
+        self.model.len()
+    }
+
+    fn item(&self, index: usize) -> Option<&I> {
+        self.model.item(index)
+    }
+
+    fn item_mut(&mut self, index: usize) -> Option<&mut I> {
+        self.model.item_mut(index)
+    }
+
+    fn add(&mut self, item: I) {
+        self.model.add(item)
+    }
+
+    fn insert(&mut self, index: usize, item: I) {
+        self.model.insert(index, item)
+    }
+
+    fn remove(&mut self, index: usize) {
+        self.model.remove(index)
+    }
+
+    fn swap_items(&mut self, a: usize, b: usize) {
+        self.model.swap_items(a, b)
+    }
+
+    fn copy(&mut self, a: usize, b: usize) {
+        self.model.copy(a, b)
+    }
+
+    fn move_item(&mut self, from: usize, to: usize) {
+        self.model.move_item(from, to)
+    }
+
+    fn remove_many(&mut self, indices: &[usize]) {
+        self.model.remove_many(indices)
+    }
+
+    fn row_to_index(&self, row_number: usize) -> Option<usize> {
+        self.model.row_to_index(row_number)
+    }
+
+    fn generation(&self) -> Option<u64> {
+        self.model.generation()
+    }
+
+    fn try_add(&mut self, item: I) -> Result<(), ModelError> {
+        self.model.try_add(item)
+    }
+
+    fn try_insert(&mut self, index: usize, item: I) -> Result<(), ModelError> {
+        self.model.try_insert(index, item)
+    }
+}
+
+impl<M, I> KeyedListModel<I> for AliasModel<M, I>
+where
+    M: KeyedListModel<I>,
+    I: KeyedViewItem,
+{
+    fn index_of(&self, key: &str) -> Option<usize> {
+        self.model.index_of(key).or_else(|| {
+            self.aliases
+                .get(key)
+                .and_then(|target| self.model.index_of(target))
+        })
+    }
+}
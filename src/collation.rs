@@ -0,0 +1,133 @@
+use std::cmp::Ordering;
+
+/// Controls how display text is compared when sorting and searching.
+pub trait TextCollator {
+    /// Compare two strings for sorting.
+    fn compare(&self, a: &str, b: &str) -> Ordering;
+
+    /// Whether `text` matches the search string `search`.
+    fn matches(&self, text: &str, search: &str) -> bool;
+}
+
+/// Compares strings by code point and searches case insensitively.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct OrdinalCollator;
+
+impl TextCollator for OrdinalCollator {
+    fn compare(&self, a: &str, b: &str) -> Ordering {
+        a.cmp(b)
+    }
+
+    fn matches(&self, text: &str, search: &str) -> bool {
+        text.to_lowercase().contains(&search.to_lowercase())
+    }
+}
+
+/// Compares strings using the collation rules of a locale.
+/// Searching ignores case and accents, so "e" will match "É".
+#[cfg(feature = "icu")]
+pub struct LocaleCollator {
+    sort_collator: icu_collator::CollatorBorrowed<'static>,
+    search_collator: icu_collator::CollatorBorrowed<'static>,
+}
+
+#[cfg(feature = "icu")]
+impl LocaleCollator {
+    /// Create a collator from a BCP-47 locale identifier such as "en" or "ja-JP".
+    /// Returns [None] if the locale is invalid or has no collation data.
+    pub fn new(locale: &str) -> Option<Self> {
+        use icu_collator::options::{CollatorOptions, Strength};
+        use icu_collator::Collator;
+        use icu_locale_core::Locale;
+
+        let locale = Locale::try_from_str(locale).ok()?;
+        let sort_collator = Collator::try_new((&locale).into(), CollatorOptions::default()).ok()?;
+        let mut search_options = CollatorOptions::default();
+        search_options.strength = Some(Strength::Primary);
+        let search_collator = Collator::try_new((&locale).into(), search_options).ok()?;
+        Some(Self {
+            sort_collator,
+            search_collator,
+        })
+    }
+}
+
+#[cfg(feature = "icu")]
+impl TextCollator for LocaleCollator {
+    fn compare(&self, a: &str, b: &str) -> Ordering {
+        self.sort_collator.compare(a, b)
+    }
+
+    fn matches(&self, text: &str, search: &str) -> bool {
+        let search_len = search.chars().count();
+        if search_len == 0 {
+            return true;
+        }
+        // Compare every window of the text that has the same number of characters as the search string.
+        let boundaries: Vec<usize> = text
+            .char_indices()
+            .map(|(i, _)| i)
+            .chain(std::iter::once(text.len()))
+            .collect();
+        boundaries.windows(search_len + 1).any(|window| {
+            let candidate = &text[window[0]..window[search_len]];
+            self.search_collator.compare(candidate, search) == Ordering::Equal
+        })
+    }
+}
+
+/// Sorts runs of digits by their numeric value, so "Chapter2" comes before "Chapter10".
+/// Searches case insensitively.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct NaturalCollator;
+
+impl TextCollator for NaturalCollator {
+    fn compare(&self, a: &str, b: &str) -> Ordering {
+        natural_cmp(a, b)
+    }
+
+    fn matches(&self, text: &str, search: &str) -> bool {
+        OrdinalCollator.matches(text, search)
+    }
+}
+
+/// Compare two strings, treating runs of ASCII digits as numbers.
+pub fn natural_cmp(a: &str, b: &str) -> Ordering {
+    let mut a_chars = a.chars().peekable();
+    let mut b_chars = b.chars().peekable();
+    loop {
+        match (a_chars.peek().copied(), b_chars.peek().copied()) {
+            (None, None) => return a.cmp(b),
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(x), Some(y)) if x.is_ascii_digit() && y.is_ascii_digit() => {
+                let x_digits = take_digits(&mut a_chars);
+                let y_digits = take_digits(&mut b_chars);
+                let x_trimmed = x_digits.trim_start_matches('0');
+                let y_trimmed = y_digits.trim_start_matches('0');
+                let ordering = x_trimmed
+                    .len()
+                    .cmp(&y_trimmed.len())
+                    .then_with(|| x_trimmed.cmp(y_trimmed));
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+            }
+            (Some(x), Some(y)) => {
+                if x != y {
+                    return x.cmp(&y);
+                }
+                a_chars.next();
+                b_chars.next();
+            }
+        }
+    }
+}
+
+fn take_digits(chars: &mut std::iter::Peekable<std::str::Chars>) -> String {
+    let mut digits = String::new();
+    while let Some(c) = chars.next_if(|c| c.is_ascii_digit()) {
+        digits.push(c);
+    }
+    digits
+}
@@ -0,0 +1,115 @@
+use std::cell::RefCell;
+
+use crate::ListModel;
+
+type ItemEdit<I> = Box<dyn FnOnce(&mut I)>;
+
+/// A change to a [ListModel] waiting in a [CommandQueue].
+pub enum ModelCommand<I> {
+    Add(I),
+    Insert(usize, I),
+    Remove(usize),
+    Swap(usize, usize),
+    Move { from: usize, to: usize },
+    Copy(usize, usize),
+    Edit(usize, ItemEdit<I>),
+}
+
+/// Collects model changes requested while the model is borrowed, ex. from inside a popup
+/// which is iterating the model, so they can be applied after the frame with [CommandQueue::apply].
+///
+/// Commands are pushed through a shared reference, so several closures can hold the same queue.
+/// Indices refer to the model as it will be when the command runs, after the commands before it.
+pub struct CommandQueue<I> {
+    commands: RefCell<Vec<ModelCommand<I>>>,
+}
+
+impl<I> Default for CommandQueue<I> {
+    fn default() -> Self {
+        Self {
+            commands: RefCell::new(vec![]),
+        }
+    }
+}
+
+impl<I> CommandQueue<I> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&self, command: ModelCommand<I>) {
+        self.commands.borrow_mut().push(command);
+    }
+
+    pub fn add(&self, item: I) {
+        self.push(ModelCommand::Add(item));
+    }
+
+    pub fn insert(&self, index: usize, item: I) {
+        self.push(ModelCommand::Insert(index, item));
+    }
+
+    pub fn remove(&self, index: usize) {
+        self.push(ModelCommand::Remove(index));
+    }
+
+    pub fn swap_items(&self, a: usize, b: usize) {
+        self.push(ModelCommand::Swap(a, b));
+    }
+
+    pub fn move_item(&self, from: usize, to: usize) {
+        self.push(ModelCommand::Move { from, to });
+    }
+
+    pub fn copy(&self, a: usize, b: usize) {
+        self.push(ModelCommand::Copy(a, b));
+    }
+
+    /// Modify the item at the index once the queue is applied.
+    pub fn edit(&self, index: usize, edit: impl FnOnce(&mut I) + 'static) {
+        self.push(ModelCommand::Edit(index, Box::new(edit)));
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.commands.borrow().is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.commands.borrow().len()
+    }
+
+    /// Discard every pending command.
+    pub fn clear(&self) {
+        self.commands.borrow_mut().clear();
+    }
+
+    /// Run every pending command against the model in the order they were pushed.
+    /// Returns the number of commands which succeeded. Commands the model rejects,
+    /// ex. out of bounds indices or edits to rows which no longer exist, are skipped.
+    pub fn apply<M>(&self, model: &mut M) -> usize
+    where
+        M: ListModel<I>,
+    {
+        let commands = std::mem::take(&mut *self.commands.borrow_mut());
+        let mut count = 0;
+        for command in commands {
+            let applied = match command {
+                ModelCommand::Add(item) => model.try_add(item).is_ok(),
+                ModelCommand::Insert(index, item) => model.try_insert(index, item).is_ok(),
+                ModelCommand::Remove(index) => model.try_remove(index).is_ok(),
+                ModelCommand::Swap(a, b) => model.try_swap_items(a, b).is_ok(),
+                ModelCommand::Move { from, to } => model.try_move_item(from, to).is_ok(),
+                ModelCommand::Copy(a, b) => model.try_copy(a, b).is_ok(),
+                ModelCommand::Edit(index, edit) => match model.item_mut(index) {
+                    Some(item) => {
+                        edit(item);
+                        true
+                    }
+                    None => false,
+                },
+            };
+            count += usize::from(applied);
+        }
+        count
+    }
+}
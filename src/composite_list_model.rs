@@ -0,0 +1,185 @@
+use crate::ListModel;
+
+/// Presents several [ListModel]s as one contiguous list, ex. built-in items followed by user items.
+///
+/// Mutations are routed to the source that owns the index. [ListModel::add] appends to the last source.
+pub struct CompositeListModel<'a, I> {
+    sources: Vec<Box<dyn ListModel<I> + 'a>>,
+    // Advances on changes the sources don't see, ex. adding a source.
+    generation: u64,
+}
+
+impl<I> Default for CompositeListModel<'_, I> {
+    fn default() -> Self {
+        Self {
+            sources: vec![],
+            generation: 0,
+        }
+    }
+}
+
+impl<'a, I> CompositeListModel<'a, I> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a source after the existing sources. Pass `&mut model` to borrow a model instead of moving it.
+    pub fn with_source(mut self, source: impl ListModel<I> + 'a) -> Self {
+        self.push_source(source);
+        self
+    }
+
+    pub fn push_source(&mut self, source: impl ListModel<I> + 'a) {
+        self.sources.push(Box::new(source));
+        self.bump();
+    }
+
+    pub fn source_count(&self) -> usize {
+        self.sources.len()
+    }
+
+    pub fn source(&self, source: usize) -> Option<&(dyn ListModel<I> + 'a)> {
+        self.sources.get(source).map(|source| source.as_ref())
+    }
+
+    /// Mutably access a source. Advances the [ListModel::generation] since the changes can't be tracked.
+    pub fn source_mut(&mut self, source: usize) -> Option<&mut (dyn ListModel<I> + 'a)> {
+        self.bump();
+        self.sources.get_mut(source).map(|source| source.as_mut())
+    }
+
+    /// The index of the first item from the given source.
+    pub fn source_offset(&self, source: usize) -> usize {
+        self.sources.iter().take(source).map(|s| s.len()).sum()
+    }
+
+    /// Convert an index into the source that owns it and the index within that source.
+    pub fn locate(&self, index: usize) -> Option<(usize, usize)> {
+        let mut offset = 0;
+        for (source, model) in self.sources.iter().enumerate() {
+            if index < offset + model.len() {
+                return Some((source, index - offset));
+            }
+            offset += model.len();
+        }
+        None
+    }
+
+    fn bump(&mut self) {
+        self.generation = self.generation.wrapping_add(1);
+    }
+}
+
+impl<I> ListModel<I> for CompositeListModel<'_, I>
+where
+    I: Clone,
+{
+    fn is_empty(&self) -> bool {
+        self.sources.iter().all(|source| source.is_empty())
+    }
+
+    fn len(&self) -> usize {
+        self.sources.iter().map(|source| source.len()).sum()
+    }
+
+    fn item(&self, index: usize) -> Option<&I> {
+        let (source, index) = self.locate(index)?;
+        self.sources[source].item(index)
+    }
+
+    fn item_mut(&mut self, index: usize) -> Option<&mut I> {
+        let (source, index) = self.locate(index)?;
+        self.sources[source].item_mut(index)
+    }
+
+    fn add(&mut self, item: I) {
+        if let Some(source) = self.sources.last_mut() {
+            source.add(item);
+        }
+    }
+
+    fn insert(&mut self, index: usize, item: I) {
+        match self.locate(index) {
+            Some((source, index)) => self.sources[source].insert(index, item),
+            None if index == self.len() => self.add(item),
+            None => {}
+        }
+    }
+
+    fn remove(&mut self, index: usize) {
+        if let Some((source, index)) = self.locate(index) {
+            self.sources[source].remove(index);
+        }
+    }
+
+    fn swap_items(&mut self, a: usize, b: usize) {
+        let (Some((source_a, index_a)), Some((source_b, index_b))) =
+            (self.locate(a), self.locate(b))
+        else {
+            return;
+        };
+        if source_a == source_b {
+            self.sources[source_a].swap_items(index_a, index_b);
+        } else if let (Some(item_a), Some(item_b)) = (self.item(a).cloned(), self.item(b).cloned())
+        {
+            if let Some(item) = self.sources[source_a].item_mut(index_a) {
+                *item = item_b;
+            }
+            if let Some(item) = self.sources[source_b].item_mut(index_b) {
+                *item = item_a;
+            }
+            self.bump();
+        }
+    }
+
+    fn copy(&mut self, a: usize, b: usize) {
+        let (Some((source_a, index_a)), Some((source_b, index_b))) =
+            (self.locate(a), self.locate(b))
+        else {
+            return;
+        };
+        if source_a == source_b {
+            self.sources[source_a].copy(index_a, index_b);
+        } else if let Some(item) = self.item(a).cloned() {
+            if let Some(target) = self.sources[source_b].item_mut(index_b) {
+                *target = item;
+                self.bump();
+            }
+        }
+    }
+
+    fn move_item(&mut self, from: usize, to: usize) {
+        let (Some((source_from, index_from)), Some((source_to, index_to))) =
+            (self.locate(from), self.locate(to))
+        else {
+            return;
+        };
+        if source_from == source_to {
+            self.sources[source_from].move_item(index_from, index_to);
+        } else if from < to {
+            for i in from..to {
+                self.swap_items(i, i + 1);
+            }
+        } else {
+            for i in (to..from).rev() {
+                self.swap_items(i, i + 1);
+            }
+        }
+    }
+
+    fn row_to_index(&self, row_number: usize) -> Option<usize> {
+        let (source, row_number) = self.locate(row_number)?;
+        self.sources[source]
+            .row_to_index(row_number)
+            .map(|index| self.source_offset(source) + index)
+    }
+
+    /// Combines the generations of the sources, so it's [None] if any source doesn't report one.
+    fn generation(&self) -> Option<u64> {
+        self.sources
+            .iter()
+            .try_fold(self.generation, |generation, source| {
+                Some(generation.wrapping_add(source.generation()?))
+            })
+    }
+}
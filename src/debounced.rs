@@ -0,0 +1,93 @@
+use std::time::Duration;
+
+use egui::{InnerResponse, Response, Ui};
+
+/// A binding which widgets write into immediately, but which only commits the value
+/// after a quiet period or when the widget loses focus. Use it to avoid re-running
+/// expensive work (parsing, rendering, filtering) on every keystroke.
+#[derive(Debug, Clone)]
+pub struct Debounced<T> {
+    pending: T,
+    committed: T,
+    delay: f64,
+    last_edit: Option<f64>,
+}
+
+impl<T> Debounced<T>
+where
+    T: Clone + PartialEq,
+{
+    pub fn new(value: T) -> Self {
+        Self {
+            pending: value.clone(),
+            committed: value,
+            delay: 0.3,
+            last_edit: None,
+        }
+    }
+
+    /// The quiet period after the last edit before the value is committed. Defaults to 0.3 seconds.
+    pub fn with_delay(mut self, delay: Duration) -> Self {
+        self.delay = delay.as_secs_f64();
+        self
+    }
+
+    /// The value which downstream work should use.
+    pub fn committed(&self) -> &T {
+        &self.committed
+    }
+
+    /// The value shown in the widget, which may not be committed yet.
+    pub fn pending(&self) -> &T {
+        &self.pending
+    }
+
+    /// Whether there are edits which haven't been committed.
+    pub fn is_pending(&self) -> bool {
+        self.pending != self.committed
+    }
+
+    /// Replace the value and commit it immediately.
+    pub fn set(&mut self, value: T) {
+        self.pending = value.clone();
+        self.committed = value;
+        self.last_edit = None;
+    }
+
+    /// Commit pending edits immediately. Returns true if the committed value changed.
+    pub fn commit(&mut self) -> bool {
+        self.last_edit = None;
+        if self.is_pending() {
+            self.committed = self.pending.clone();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Show a widget bound to the pending value. The inner value is true on the frame the value is committed.
+    pub fn show(
+        &mut self,
+        ui: &mut Ui,
+        add_widget: impl FnOnce(&mut Ui, &mut T) -> Response,
+    ) -> InnerResponse<bool> {
+        let response = add_widget(ui, &mut self.pending);
+        let now = ui.input(|i| i.time);
+        if response.changed() {
+            self.last_edit = Some(now);
+        }
+        let committed = match self.last_edit {
+            _ if response.lost_focus() => self.commit(),
+            Some(last_edit) if now - last_edit >= self.delay => self.commit(),
+            Some(last_edit) => {
+                // Wake up when the quiet period ends in case there is no other input.
+                let remaining = self.delay - (now - last_edit);
+                ui.ctx()
+                    .request_repaint_after(Duration::from_secs_f64(remaining.max(0.)));
+                false
+            }
+            None => false,
+        };
+        InnerResponse::new(committed, response)
+    }
+}
@@ -0,0 +1,98 @@
+use std::cell::RefCell;
+use std::hash::Hash;
+
+use egui::{ColorImage, Context, Image, TextureHandle, TextureOptions};
+use indexmap::IndexMap;
+
+/// Loads decoration images on demand, ex. cutting icons out of a sprite sheet.
+pub trait DecorationSource {
+    /// Identifies one decoration, ex. a sprite index.
+    type Key: Hash + Eq + Clone;
+
+    /// Decode the image for the key. Returns [None] if the key has no image.
+    fn load(&self, key: &Self::Key) -> Option<ColorImage>;
+
+    /// A debug name for the texture created for the key.
+    fn texture_name(&self, key: &Self::Key) -> String {
+        let _ = key;
+        "decoration".to_string()
+    }
+}
+
+/// Keeps the most recently used textures loaded from a [DecorationSource] so items don't
+/// decode images every frame. Store it in an item type's [crate::ViewItem::DecorationDependencies]
+/// to share textures between every widget showing the items.
+///
+/// Missing images are cached too, so a key without an image is only loaded once.
+pub struct DecorationCache<S: DecorationSource> {
+    ctx: Context,
+    source: S,
+    capacity: usize,
+    options: TextureOptions,
+    // Least recently used first.
+    textures: RefCell<IndexMap<S::Key, Option<TextureHandle>>>,
+}
+
+impl<S: DecorationSource> DecorationCache<S> {
+    /// Create a cache which holds at most `capacity` textures.
+    pub fn new(ctx: &Context, source: S, capacity: usize) -> Self {
+        Self {
+            ctx: ctx.clone(),
+            source,
+            capacity: capacity.max(1),
+            options: TextureOptions::default(),
+            textures: RefCell::new(IndexMap::new()),
+        }
+    }
+
+    /// How textures are sampled, ex. [TextureOptions::NEAREST] for pixel art.
+    pub fn with_options(mut self, options: TextureOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    pub fn source(&self) -> &S {
+        &self.source
+    }
+
+    /// Mutably access the source. Every texture is reloaded afterwards.
+    pub fn source_mut(&mut self) -> &mut S {
+        self.invalidate_all();
+        &mut self.source
+    }
+
+    /// Retrieve the texture for the key, loading it if it isn't cached.
+    pub fn texture(&self, key: &S::Key) -> Option<TextureHandle> {
+        let mut textures = self.textures.borrow_mut();
+        if let Some(index) = textures.get_index_of(key) {
+            let last = textures.len() - 1;
+            textures.move_index(index, last);
+            return textures[last].clone();
+        }
+        let texture = self.source.load(key).map(|image| {
+            self.ctx
+                .load_texture(self.source.texture_name(key), image, self.options)
+        });
+        textures.insert(key.clone(), texture.clone());
+        while textures.len() > self.capacity {
+            textures.shift_remove_index(0);
+        }
+        texture
+    }
+
+    /// Retrieve the texture for the key as an [Image].
+    pub fn image(&self, key: &S::Key) -> Option<Image<'static>> {
+        self.texture(key)
+            .map(|texture| Image::from_texture(&texture))
+    }
+
+    /// Reload the key's texture the next time it is used, ex. after its sprite sheet changed.
+    pub fn invalidate(&self, key: &S::Key) {
+        self.textures.borrow_mut().shift_remove(key);
+    }
+
+    /// Reload every texture the next time it is used.
+    pub fn invalidate_all(&self) {
+        self.textures.borrow_mut().clear();
+    }
+}
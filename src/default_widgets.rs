@@ -1,4 +1,5 @@
-use egui::{DragValue, Response, Ui};
+use egui::emath::Numeric;
+use egui::{Context, DragValue, Id, Response, Ui, Vec2, Widget};
 
 use crate::editable_list;
 
@@ -23,43 +24,43 @@ impl DefaultWidget for bool {
 
 impl DefaultWidget for u8 {
     fn default_widget(&mut self, ui: &mut Ui) -> Response {
-        ui.add(DragValue::new(self))
+        numeric_default_widget(self, ui)
     }
 }
 
 impl DefaultWidget for u16 {
     fn default_widget(&mut self, ui: &mut Ui) -> Response {
-        ui.add(DragValue::new(self))
+        numeric_default_widget(self, ui)
     }
 }
 
 impl DefaultWidget for u32 {
     fn default_widget(&mut self, ui: &mut Ui) -> Response {
-        ui.add(DragValue::new(self))
+        numeric_default_widget(self, ui)
     }
 }
 
 impl DefaultWidget for i8 {
     fn default_widget(&mut self, ui: &mut Ui) -> Response {
-        ui.add(DragValue::new(self))
+        numeric_default_widget(self, ui)
     }
 }
 
 impl DefaultWidget for i16 {
     fn default_widget(&mut self, ui: &mut Ui) -> Response {
-        ui.add(DragValue::new(self))
+        numeric_default_widget(self, ui)
     }
 }
 
 impl DefaultWidget for i32 {
     fn default_widget(&mut self, ui: &mut Ui) -> Response {
-        ui.add(DragValue::new(self))
+        numeric_default_widget(self, ui)
     }
 }
 
 impl DefaultWidget for f32 {
     fn default_widget(&mut self, ui: &mut Ui) -> Response {
-        ui.add(DragValue::new(self))
+        numeric_default_widget(self, ui)
     }
 }
 
@@ -73,3 +74,158 @@ where
         }))
     }
 }
+
+/// Settings for adjusting numeric values with the mouse wheel while they are hovered.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WheelAdjust {
+    /// The amount added or subtracted per wheel step.
+    pub step: f64,
+    /// Multiplier applied to the step while holding shift.
+    pub fine_multiplier: f64,
+    /// Multiplier applied to the step while holding ctrl (cmd on Mac).
+    pub coarse_multiplier: f64,
+}
+
+impl Default for WheelAdjust {
+    fn default() -> Self {
+        Self {
+            step: 1.,
+            fine_multiplier: 0.1,
+            coarse_multiplier: 10.,
+        }
+    }
+}
+
+impl WheelAdjust {
+    fn id() -> Id {
+        Id::new("__thane_wheel_adjust")
+    }
+
+    /// Enable wheel adjustment on numeric [DefaultWidget]s for this context.
+    pub fn enable(self, ctx: &Context) {
+        ctx.data_mut(|data| data.insert_temp(Self::id(), self));
+    }
+
+    /// Disable wheel adjustment on numeric [DefaultWidget]s for this context.
+    pub fn disable(ctx: &Context) {
+        ctx.data_mut(|data| data.remove::<Self>(Self::id()));
+    }
+
+    /// The settings used by numeric [DefaultWidget]s, if wheel adjustment is enabled.
+    pub fn get(ctx: &Context) -> Option<Self> {
+        ctx.data(|data| data.get_temp(Self::id()))
+    }
+
+    /// Adjust `value` if `response` is hovered and the mouse wheel moved.
+    /// Consumes the scroll so that parent scroll areas do not move.
+    pub fn apply<N: Numeric>(&self, ui: &Ui, response: &mut Response, value: &mut N) {
+        if !response.hovered() {
+            return;
+        }
+        let (delta, modifiers) = ui.input(|input| (input.raw_scroll_delta, input.modifiers));
+        // Shift turns vertical scrolling into horizontal scrolling, so look at both axes.
+        let delta = delta.x + delta.y;
+        if delta == 0. {
+            return;
+        }
+        ui.input_mut(|input| {
+            input.raw_scroll_delta = Vec2::ZERO;
+            input.smooth_scroll_delta = Vec2::ZERO;
+        });
+
+        let mut step = self.step;
+        if modifiers.command {
+            step *= self.coarse_multiplier;
+        } else if modifiers.shift {
+            step *= self.fine_multiplier;
+        }
+        if N::INTEGRAL {
+            step = step.round().max(1.);
+        }
+        let new_value =
+            (value.to_f64() + step * delta.signum() as f64).clamp(N::MIN.to_f64(), N::MAX.to_f64());
+        let new_value = N::from_f64(new_value);
+        if new_value != *value {
+            *value = new_value;
+            response.mark_changed();
+        }
+    }
+}
+
+/// A [DragValue] which can also be adjusted with the mouse wheel while hovered.
+pub fn wheel_drag_value<N: Numeric>(value: &mut N, adjust: WheelAdjust) -> impl Widget + '_ {
+    move |ui: &mut Ui| {
+        let mut response = ui.add(DragValue::new(value));
+        adjust.apply(ui, &mut response, value);
+        response
+    }
+}
+
+fn numeric_default_widget<N: Numeric>(value: &mut N, ui: &mut Ui) -> Response {
+    match WheelAdjust::get(ui.ctx()) {
+        Some(adjust) => ui.add(wheel_drag_value(value, adjust)),
+        None => ui.add(DragValue::new(value)),
+    }
+}
+
+/// A linear conversion between the unit a value is stored in and the unit it is displayed in.
+/// `displayed = stored * scale + offset`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct UnitConversion {
+    pub scale: f64,
+    pub offset: f64,
+    pub suffix: &'static str,
+}
+
+impl UnitConversion {
+    /// Display a fraction (0-1) as a percentage (0-100).
+    pub const PERCENT: Self = Self::scale(100.).with_suffix("%");
+
+    pub const fn scale(scale: f64) -> Self {
+        Self {
+            scale,
+            offset: 0.,
+            suffix: "",
+        }
+    }
+
+    pub const fn with_offset(mut self, offset: f64) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    pub const fn with_suffix(mut self, suffix: &'static str) -> Self {
+        self.suffix = suffix;
+        self
+    }
+
+    pub fn to_display(&self, stored: f64) -> f64 {
+        stored * self.scale + self.offset
+    }
+
+    pub fn from_display(&self, displayed: f64) -> f64 {
+        (displayed - self.offset) / self.scale
+    }
+}
+
+/// A [DragValue] which displays and edits a value in a different unit than it is stored in.
+pub fn converted_drag_value<N: Numeric>(
+    value: &mut N,
+    conversion: UnitConversion,
+) -> impl Widget + '_ {
+    move |ui: &mut Ui| {
+        let mut displayed = conversion.to_display(value.to_f64());
+        let mut response = ui.add(DragValue::new(&mut displayed).suffix(conversion.suffix));
+        if let Some(adjust) = WheelAdjust::get(ui.ctx()) {
+            adjust.apply(ui, &mut response, &mut displayed);
+        }
+        if response.changed() {
+            let mut stored = conversion.from_display(displayed);
+            if N::INTEGRAL {
+                stored = stored.round();
+            }
+            *value = N::from_f64(stored.clamp(N::MIN.to_f64(), N::MAX.to_f64()));
+        }
+        response
+    }
+}
@@ -0,0 +1,238 @@
+use std::collections::BTreeSet;
+
+use crate::{KeyedListModel, KeyedViewItem, ListModel, ModelError, ModelEvent, ViewItem};
+
+/// Wraps a [ListModel] and records which items were changed since the last call to
+/// [DirtyTrackingModel::clear_dirty]. Save logic can write only the changed records,
+/// and widgets can mark modified rows (see [crate::RowLayout::modified]).
+///
+/// Items accessed with [ListModel::item_mut] are assumed to be modified.
+/// Dirty indices follow their items when other items are inserted, removed, or reordered.
+pub struct DirtyTrackingModel<M, I> {
+    model: M,
+    dirty: BTreeSet<usize>,
+    removed: Vec<I>,
+}
+
+impl<M, I> DirtyTrackingModel<M, I>
+where
+    M: ListModel<I>,
+    I: ViewItem,
+{
+    pub fn new(model: M) -> Self {
+        Self {
+            model,
+            dirty: BTreeSet::new(),
+            removed: vec![],
+        }
+    }
+
+    pub fn inner(&self) -> &M {
+        &self.model
+    }
+
+    pub fn into_inner(self) -> M {
+        self.model
+    }
+
+    /// Whether anything was changed or removed since the last call to [DirtyTrackingModel::clear_dirty].
+    pub fn is_modified(&self) -> bool {
+        !self.dirty.is_empty() || !self.removed.is_empty()
+    }
+
+    pub fn is_dirty(&self, index: usize) -> bool {
+        self.dirty.contains(&index)
+    }
+
+    /// The indices of every changed or added item in ascending order.
+    pub fn dirty_indices(&self) -> impl Iterator<Item = usize> + '_ {
+        self.dirty.iter().copied()
+    }
+
+    /// Items which were removed since the last call to [DirtyTrackingModel::clear_dirty].
+    pub fn removed(&self) -> &[I] {
+        &self.removed
+    }
+
+    /// Record a change made outside of the [ListModel] API.
+    pub fn mark_dirty(&mut self, index: usize) {
+        if index < self.model.len() {
+            self.dirty.insert(index);
+        }
+    }
+
+    /// Forget every recorded change, ex. after saving.
+    pub fn clear_dirty(&mut self) {
+        self.dirty.clear();
+        self.removed.clear();
+    }
+
+    fn shift(&mut self, event: ModelEvent) {
+        self.dirty = self
+            .dirty
+            .iter()
+            .filter_map(|index| event.map_index(*index))
+            .collect();
+    }
+}
+
+impl<M, I> DirtyTrackingModel<M, I>
+where
+    M: KeyedListModel<I>,
+    I: KeyedViewItem,
+{
+    /// The keys of every changed or added item.
+    pub fn dirty_keys(&self) -> Vec<String> {
+        self.dirty
+            .iter()
+            .filter_map(|index| self.model.item(*index))
+            .map(|item| item.key().into_owned())
+            .collect()
+    }
+
+    /// The keys of every removed item.
+    pub fn removed_keys(&self) -> Vec<String> {
+        self.removed
+            .iter()
+            .map(|item| item.key().into_owned())
+            .collect()
+    }
+}
+
+impl<M, I> ListModel<I> for DirtyTrackingModel<M, I>
+where
+    M: ListModel<I>,
+    I: ViewItem,
+{
+    fn is_empty(&self) -> bool {
+        self.model.is_empty()
+    }
+
+    fn len(&self) -> usize {
+        self.model.len()
+    }
+
+    fn item(&self, index: usize) -> Option<&I> {
+        self.model.item(index)
+    }
+
+    fn item_mut(&mut self, index: usize) -> Option<&mut I> {
+        let item = self.model.item_mut(index)?;
+        self.dirty.insert(index);
+        Some(item)
+    }
+
+    fn add(&mut self, item: I) {
+        let len = self.model.len();
+        self.model.add(item);
+        if self.model.len() > len {
+            self.dirty.insert(len);
+        }
+    }
+
+    fn insert(&mut self, index: usize, item: I) {
+        let len = self.model.len();
+        self.model.insert(index, item);
+        if self.model.len() > len {
+            self.shift(ModelEvent::Inserted(index));
+            self.dirty.insert(index);
+        }
+    }
+
+    fn remove(&mut self, index: usize) {
+        if let Some(item) = self.model.item(index).cloned() {
+            self.model.remove(index);
+            self.shift(ModelEvent::Removed(index));
+            self.removed.push(item);
+        }
+    }
+
+    fn swap_items(&mut self, a: usize, b: usize) {
+        if a < self.model.len() && b < self.model.len() {
+            self.model.swap_items(a, b);
+            self.shift(ModelEvent::Swapped(a, b));
+            // Order is part of the data for most formats, so both positions changed.
+            self.dirty.insert(a);
+            self.dirty.insert(b);
+        }
+    }
+
+    fn move_item(&mut self, from: usize, to: usize) {
+        if from < self.model.len() && to < self.model.len() && from != to {
+            self.model.move_item(from, to);
+            self.shift(ModelEvent::Moved { from, to });
+            // Every item between the two positions changed position.
+            self.dirty.extend(from.min(to)..=from.max(to));
+        }
+    }
+
+    fn copy(&mut self, a: usize, b: usize) {
+        if a < self.model.len() && b < self.model.len() {
+            self.model.copy(a, b);
+            self.dirty.insert(b);
+        }
+    }
+
+    fn row_to_index(&self, row_number: usize) -> Option<usize> {
+        self.model.row_to_index(row_number)
+    }
+
+    fn generation(&self) -> Option<u64> {
+        self.model.generation()
+    }
+
+    fn try_add(&mut self, item: I) -> Result<(), ModelError> {
+        let len = self.model.len();
+        self.model.try_add(item)?;
+        self.dirty.insert(len);
+        Ok(())
+    }
+
+    fn try_insert(&mut self, index: usize, item: I) -> Result<(), ModelError> {
+        self.model.try_insert(index, item)?;
+        self.shift(ModelEvent::Inserted(index));
+        self.dirty.insert(index);
+        Ok(())
+    }
+
+    fn try_remove(&mut self, index: usize) -> Result<(), ModelError> {
+        let item = self.model.item(index).cloned();
+        self.model.try_remove(index)?;
+        self.shift(ModelEvent::Removed(index));
+        self.removed.extend(item);
+        Ok(())
+    }
+
+    fn try_swap_items(&mut self, a: usize, b: usize) -> Result<(), ModelError> {
+        self.model.try_swap_items(a, b)?;
+        self.shift(ModelEvent::Swapped(a, b));
+        self.dirty.insert(a);
+        self.dirty.insert(b);
+        Ok(())
+    }
+
+    fn try_move_item(&mut self, from: usize, to: usize) -> Result<(), ModelError> {
+        self.model.try_move_item(from, to)?;
+        if from != to {
+            self.shift(ModelEvent::Moved { from, to });
+            self.dirty.extend(from.min(to)..=from.max(to));
+        }
+        Ok(())
+    }
+
+    fn try_copy(&mut self, a: usize, b: usize) -> Result<(), ModelError> {
+        self.model.try_copy(a, b)?;
+        self.dirty.insert(b);
+        Ok(())
+    }
+}
+
+impl<M, I> KeyedListModel<I> for DirtyTrackingModel<M, I>
+where
+    M: KeyedListModel<I>,
+    I: KeyedViewItem,
+{
+    fn index_of(&self, key: &str) -> Option<usize> {
+        self.model.index_of(key)
+    }
+}
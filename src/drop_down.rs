@@ -1,6 +1,13 @@
-use egui::{AboveOrBelow, Grid, PopupCloseBehavior, Response, ScrollArea, Sense, Ui, Widget};
+use egui::{
+    Align, Button, Color32, Grid, Id, PopupCloseBehavior, Response, ScrollArea, Sense, TextStyle,
+    Ui, Widget,
+};
 
-use crate::{DecorationKind, KeyedListModel, KeyedViewItem, ListModel, ViewItem};
+use crate::{
+    emit_event, fuzzy_score, group_header, group_rows, DecorationKind, KeyPolicy, KeyedListModel,
+    KeyedViewItem, ListModel, MatchMode, NavigationEvent, OrdinalCollator, RowLayout,
+    SearchTextCache, SearchablePopup, SelectionModel, SortKey, TextCollator, ViewItem,
+};
 
 pub fn model_drop_down<'a, M, I, DD>(
     model: &'a M,
@@ -28,44 +35,79 @@ where
     }
 }
 
-fn drop_down_item_ui<M, I, DD>(
-    ui: &mut Ui,
-    model: &M,
-    decoration_dependencies: &DD,
-    index: usize,
-    selected: bool,
-) -> Response
-where
-    M: ListModel<I>,
-    I: ViewItem<DecorationDependencies = DD>,
-{
-    if let Some(item) = model.item(index) {
-        if let Some(item) = model.item(index) {
-            item.with_decoration(decoration_dependencies, DecorationKind::DropDown, |image| {
-                match image {
-                    Some(image) => ui.add(image),
-                    None => ui.label(""),
-                }
-            });
-        } else {
-            ui.label("");
-        }
-        item.with_text(|text| ui.selectable_label(selected, text))
-    } else {
-        // Out of bounds - fill with empty space.
-        ui.label("");
-        ui.label("")
+const DANGLING_KEY_ID: &str = "__thane_dangling_key";
+
+type CreateKey<'a> = &'a dyn Fn(&str) -> Option<String>;
+
+/// Extra state reported by [ModelDropDown] through its [Response].
+pub trait DropDownResponseExt {
+    /// True if the drop down's key could not be found in the model (ex. a stale reference after a rename or delete).
+    fn dangling_key(&self) -> bool;
+}
+
+impl DropDownResponseExt for Response {
+    fn dangling_key(&self) -> bool {
+        self.ctx
+            .data(|data| data.get_temp::<bool>(self.id.with(DANGLING_KEY_ID)))
+            .unwrap_or_default()
     }
 }
 
-#[derive(Default)]
-pub struct ModelDropDown<'a> {
+/// Controls the width of a drop down's text field.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WidthPolicy {
+    /// Use a fixed width.
+    Fixed(f32),
+    /// Wide enough to show the longest item's text without truncation.
+    FitLongestItem,
+    /// Take up all available width.
+    FillAvailable,
+}
+
+/// A drop down for picking items from a model. `I` is the model's item type.
+pub struct ModelDropDown<'a, I> {
     key_transform: Option<&'a dyn Fn(&str) -> String>,
     key_reverse_transform: Option<&'a dyn Fn(&str) -> String>,
     force_refresh: bool,
+    close_behavior: Option<PopupCloseBehavior>,
+    width_policy: Option<WidthPolicy>,
+    row_layout: RowLayout<'a>,
+    allow_clear: bool,
+    clear_text: Option<&'a str>,
+    collator: Option<&'a dyn TextCollator>,
+    sorted: bool,
+    filter: Option<&'a dyn Fn(&I) -> bool>,
+    label: Option<&'a str>,
+    navigation_model_id: Option<&'a str>,
+    search_cache: Option<&'a SearchTextCache>,
+    match_mode: MatchMode,
+    checklist: bool,
+}
+
+impl<I> Default for ModelDropDown<'_, I> {
+    fn default() -> Self {
+        Self {
+            key_transform: None,
+            key_reverse_transform: None,
+            force_refresh: false,
+            close_behavior: None,
+            width_policy: None,
+            row_layout: RowLayout::default(),
+            allow_clear: false,
+            clear_text: None,
+            collator: None,
+            sorted: false,
+            filter: None,
+            label: None,
+            navigation_model_id: None,
+            search_cache: None,
+            match_mode: MatchMode::default(),
+            checklist: false,
+        }
+    }
 }
 
-impl<'a> ModelDropDown<'a> {
+impl<'a, I> ModelDropDown<'a, I> {
     pub fn transform(
         mut self,
         transform: &'a dyn Fn(&str) -> String,
@@ -81,149 +123,437 @@ impl<'a> ModelDropDown<'a> {
         self
     }
 
-    fn show_impl<M, I, DD>(
+    /// Control when the popup closes.
+    /// By default, the popup closes when an item is selected or when the user clicks outside of it.
+    /// [PopupCloseBehavior::CloseOnClickOutside] and [PopupCloseBehavior::IgnoreClicks] leave the popup
+    /// open after a selection, which is useful for picking several items in a row.
+    pub fn close_behavior(mut self, close_behavior: PopupCloseBehavior) -> Self {
+        self.close_behavior = Some(close_behavior);
+        self
+    }
+
+    /// Control the width of the text field. Defaults to [egui::style::Spacing::text_edit_width].
+    pub fn width(mut self, width_policy: WidthPolicy) -> Self {
+        self.width_policy = Some(width_policy);
+        self
+    }
+
+    /// Configure how rows in the popup are laid out.
+    pub fn row_layout(mut self, row_layout: RowLayout<'a>) -> Self {
+        self.row_layout = row_layout;
+        self
+    }
+
+    /// Show an entry at the top of the popup which clears the selection.
+    pub fn allow_clear(mut self, allow_clear: bool) -> Self {
+        self.allow_clear = allow_clear;
+        self
+    }
+
+    /// Set the label of the entry used to clear the selection. Defaults to "(None)".
+    pub fn clear_text(mut self, clear_text: &'a str) -> Self {
+        self.clear_text = Some(clear_text);
+        self
+    }
+
+    /// Use a custom [TextCollator] to match the search text and sort items.
+    /// By default, searches are case sensitive and items are sorted by code point.
+    pub fn collator(mut self, collator: &'a dyn TextCollator) -> Self {
+        self.collator = Some(collator);
+        self
+    }
+
+    /// Display items in sorted order instead of model order. Items are ordered by [ViewItem::sort_key].
+    pub fn sorted(mut self, sorted: bool) -> Self {
+        self.sorted = sorted;
+        self
+    }
+
+    /// Hide items based on caller state. The callback receives each item in the model
+    /// and must return true if the item should be displayed.
+    pub fn filter(mut self, filter: &'a dyn Fn(&I) -> bool) -> Self {
+        self.filter = Some(filter);
+        self
+    }
+
+    /// A label describing the drop down to screen readers.
+    pub fn label(mut self, label: &'a str) -> Self {
+        self.label = Some(label);
+        self
+    }
+
+    /// Show a button next to keyed drop downs which emits a [NavigationEvent] for the selected key.
+    /// Use [crate::take_events] to handle the event, ex. by opening the referenced record's editor.
+    pub fn go_to_definition(mut self, model_id: &'a str) -> Self {
+        self.navigation_model_id = Some(model_id);
+        self
+    }
+
+    /// Match the search text against cached item text instead of calling [ViewItem::with_text] for every item.
+    pub fn search_cache(mut self, cache: &'a SearchTextCache) -> Self {
+        self.search_cache = Some(cache);
+        self
+    }
+
+    /// How the search text is matched. [MatchMode::Fuzzy] ignores case and lists the closest matches first.
+    pub fn match_mode(mut self, match_mode: MatchMode) -> Self {
+        self.match_mode = match_mode;
+        self
+    }
+
+    fn search_score(&self, text: &str, search: &str) -> Option<u32> {
+        match self.match_mode {
+            MatchMode::Contains => self.matches_search(text, search).then_some(0),
+            MatchMode::Fuzzy => fuzzy_score(&text.to_lowercase(), &search.to_lowercase()),
+        }
+    }
+
+    fn matches_search(&self, text: &str, search: &str) -> bool {
+        match self.collator {
+            Some(collator) => collator.matches(text, search),
+            None => search.is_empty() || text.contains(search),
+        }
+    }
+
+    fn rows<M>(&self, model: &M) -> Vec<usize>
+    where
+        M: ListModel<I>,
+        I: ViewItem,
+    {
+        let mut rows: Vec<usize> = (0..model.len())
+            .filter(|i| match (self.filter, model.item(*i)) {
+                (Some(filter), Some(item)) => filter(item),
+                _ => true,
+            })
+            .collect();
+        if self.sorted {
+            let collator = self.collator.unwrap_or(&OrdinalCollator);
+            let mut keyed_rows: Vec<(SortKey, usize)> = rows
+                .iter()
+                .map(|i| {
+                    let key = model
+                        .item(*i)
+                        .map(ViewItem::sort_key)
+                        .unwrap_or_else(|| SortKey::Text(String::new()));
+                    (key, *i)
+                })
+                .collect();
+            keyed_rows.sort_by(|(a, _), (b, _)| a.compare(b, collator));
+            rows = keyed_rows.into_iter().map(|(_, i)| i).collect();
+        }
+        rows
+    }
+
+    fn desired_width<M>(&self, ui: &Ui, model: &M) -> Option<f32>
+    where
+        M: ListModel<I>,
+        I: ViewItem,
+    {
+        match self.width_policy? {
+            WidthPolicy::Fixed(width) => Some(width),
+            WidthPolicy::FitLongestItem => {
+                let font_id = TextStyle::Body.resolve(ui.style());
+                let longest = ui.fonts(|fonts| {
+                    model
+                        .iter()
+                        .map(|item| {
+                            item.with_text(|text| {
+                                fonts
+                                    .layout_no_wrap(
+                                        text.to_string(),
+                                        font_id.clone(),
+                                        Color32::PLACEHOLDER,
+                                    )
+                                    .size()
+                                    .x
+                            })
+                        })
+                        .fold(0., f32::max)
+                });
+                Some(longest)
+            }
+            WidthPolicy::FillAvailable => Some(f32::INFINITY),
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn show_impl<M, DD>(
         &self,
         ui: &mut Ui,
         model: &M,
         decoration_dependencies: &DD,
         selected_index: Option<usize>,
-    ) -> (Response, Option<usize>)
+        dangling_key: Option<&str>,
+        create_key: Option<CreateKey>,
+        stable_id: fn(&I) -> Option<Id>,
+    ) -> (Response, Option<Option<usize>>, Option<String>)
     where
         M: ListModel<I>,
         I: ViewItem<DecorationDependencies = DD>,
     {
-        let id = ui.auto_id_with("model_combo_box");
-        let popup_id = ui.auto_id_with("model_combo_box_popup");
+        let popup = SearchablePopup::new(ui, "model_combo_box")
+            .force_refresh(self.force_refresh)
+            .close_behavior(self.close_behavior)
+            .desired_width(self.desired_width(ui, model))
+            .label(self.label.map(|label| label.to_string()))
+            .error(dangling_key.map(|key| format!("\"{}\" was not found", key)));
         let mut selection = None;
+        let mut created = None;
 
-        let display_text = selected_index
-            .and_then(|index| model.item(index))
-            .map(|item| item.with_text(|text| text.to_string()))
-            .unwrap_or_default();
-
-        let mut search = ui.memory_mut(|mem| {
-            if mem.is_popup_open(popup_id) {
-                mem.data
-                    .get_persisted_mut_or_default::<String>(id)
-                    .to_string()
+        let is_selected = |i: usize| {
+            if self.checklist {
+                model.item(i).and_then(ViewItem::is_checked) == Some(true)
             } else {
-                display_text.to_string()
+                Some(i) == selected_index
             }
-        });
-
-        let background_color = ui.visuals().widgets.open.weak_bg_fill;
-        ui.visuals_mut().extreme_bg_color = background_color;
-
-        let text_edit_response = ui.text_edit_singleline(&mut search);
-        if text_edit_response.gained_focus() {
-            search = Default::default();
-            ui.memory_mut(|mem| {
-                mem.data.insert_persisted(id, search.clone());
-                mem.open_popup(popup_id);
-            });
-        } else if self.force_refresh {
-            search = display_text.to_string();
-            ui.memory_mut(|mem| {
-                mem.data.insert_persisted(id, search.clone());
-            });
-        } else if text_edit_response.changed() {
-            ui.memory_mut(|mem| {
-                mem.data.insert_persisted(id, search.clone());
-            });
-        }
-
-        ui.reset_style();
-
-        // Copied from egui's ComboBox implementation.
-        let above_or_below = if ui.next_widget_position().y + ui.spacing().interact_size.y + 200.0
-            < ui.ctx().screen_rect().bottom()
-        {
-            AboveOrBelow::Below
+        };
+        let none_selected = if self.checklist {
+            !(0..model.len()).any(is_selected)
         } else {
-            AboveOrBelow::Above
+            selected_index.is_none()
+        };
+        let display_text = match dangling_key {
+            Some(key) => key.to_string(),
+            None if self.checklist => (0..model.len())
+                .filter(|i| is_selected(*i))
+                .filter_map(|i| model.item(i))
+                .map(|item| item.with_text(|text| text.to_string()))
+                .collect::<Vec<_>>()
+                .join(", "),
+            None => selected_index
+                .and_then(|index| model.item(index))
+                .map(|item| item.with_text(|text| text.to_string()))
+                .unwrap_or_default(),
         };
 
-        egui::popup_above_or_below_widget(
-            ui,
-            popup_id,
-            &text_edit_response,
-            above_or_below,
-            PopupCloseBehavior::CloseOnClickOutside,
-            |ui| {
+        let text_edit_response = popup
+            .show(ui, &display_text, |ui, search| {
                 ScrollArea::vertical().show(ui, |ui| {
-                    if I::decorated(DecorationKind::DropDown) {
+                    let clear_text = self.clear_text.unwrap_or("(None)");
+                    if self.allow_clear
+                        && self.search_score(clear_text, search).is_some()
+                        && ui.selectable_label(none_selected, clear_text).clicked()
+                    {
+                        selection = Some(None);
+                        popup.select(ui, "");
+                    }
+                    if let Some(key) = create_key.and_then(|create_key| create_key(search)) {
+                        if ui
+                            .selectable_label(false, format!("Create \"{}\"", key))
+                            .clicked()
+                        {
+                            popup.select(ui, &key);
+                            created = Some(key);
+                        }
+                    }
+                    let mut show_row = |ui: &mut Ui, i: usize, text: &str| {
+                        if self.checklist {
+                            let mut checked = is_selected(i);
+                            if self.row_layout.show_check(ui, &mut checked, true).changed() {
+                                selection = Some(Some(i));
+                                popup.select(ui, text);
+                            }
+                        }
+                        let response = self.row_layout.show_item_with_id(
+                            ui,
+                            model,
+                            decoration_dependencies,
+                            DecorationKind::DropDown,
+                            i,
+                            is_selected(i),
+                            stable_id,
+                        );
+                        #[cfg(feature = "accesskit")]
+                        ui.ctx().accesskit_node_builder(response.id, |builder| {
+                            builder.set_role(egui::accesskit::Role::ListBoxOption);
+                            builder.set_selected(is_selected(i));
+                        });
+                        if popup.just_opened() && is_selected(i) {
+                            response.scroll_to_me(Some(Align::Center));
+                        }
+                        if response.clicked() {
+                            selection = Some(Some(i));
+                            popup.select(ui, text);
+                        }
+                    };
+                    // Fuzzy matching ignores case, so custom matches get the query in lowercase too.
+                    let query = match self.match_mode {
+                        MatchMode::Contains => search.to_string(),
+                        MatchMode::Fuzzy => search.to_lowercase(),
+                    };
+                    let mut rows: Vec<(usize, u32)> = self
+                        .rows(model)
+                        .into_iter()
+                        .filter_map(|i| {
+                            let item = model.item(i)?;
+                            let score =
+                                match self.search_cache.and_then(|cache| cache.text(model, i)) {
+                                    Some(text) => self.search_score(text, search),
+                                    None => item.with_text(|text| self.search_score(text, search)),
+                                };
+                            score
+                                .or_else(|| item.matches(&query).then_some(0))
+                                .map(|score| (i, score))
+                        })
+                        .collect();
+                    if self.match_mode.ranks_results() && !search.is_empty() {
+                        rows.sort_by(|(_, a), (_, b)| b.cmp(a));
+                    }
+                    let groups = group_rows(model, rows.into_iter().map(|(i, _)| i));
+                    let num_columns = self
+                        .row_layout
+                        .num_columns(I::decorated(DecorationKind::DropDown))
+                        + usize::from(self.checklist);
+                    let mut show_group = |ui: &mut Ui, key: Option<String>, rows: Vec<usize>| {
+                        if let Some(key) = key {
+                            // Expand every group while searching so matches aren't hidden.
+                            let id = popup.id().with("group").with(&key);
+                            let open = group_header(ui, id, &key, rows.len(), !search.is_empty());
+                            if num_columns > 1 {
+                                ui.end_row();
+                            }
+                            if !open {
+                                return;
+                            }
+                        }
+                        for i in rows {
+                            if let Some(item) = model.item(i) {
+                                item.with_text(|text| show_row(ui, i, text));
+                                if num_columns > 1 {
+                                    ui.end_row();
+                                }
+                            }
+                        }
+                    };
+                    if num_columns > 1 {
                         Grid::new(ui.auto_id_with("__model_combo_box_grid"))
-                            .num_columns(2)
+                            .num_columns(num_columns)
                             .show(ui, |ui| {
-                                for i in 0..model.len() {
-                                    if let Some(item) = model.item(i) {
-                                        item.with_text(|text| {
-                                            if search.is_empty() || text.contains(&search) {
-                                                let response = drop_down_item_ui(
-                                                    ui,
-                                                    model,
-                                                    decoration_dependencies,
-                                                    i,
-                                                    Some(i) == selected_index,
-                                                );
-                                                ui.end_row();
-                                                if response.clicked() {
-                                                    selection = Some(i);
-                                                    ui.memory_mut(|mem| {
-                                                        mem.data
-                                                            .insert_persisted(id, text.to_string());
-                                                        mem.close_popup();
-                                                    });
-                                                }
-                                            }
-                                        });
-                                    }
+                                for (key, rows) in groups {
+                                    show_group(ui, key, rows);
                                 }
                             });
                     } else {
-                        for i in 0..model.len() {
-                            if let Some(item) = model.item(i) {
-                                item.with_text(|text| {
-                                    if search.is_empty() || text.contains(&search) {
-                                        ui.vertical(|ui| {
-                                            if ui
-                                                .selectable_label(Some(i) == selected_index, text)
-                                                .clicked()
-                                            {
-                                                selection = Some(i);
-                                                ui.memory_mut(|mem| {
-                                                    mem.data.insert_persisted(id, text.to_string());
-                                                    mem.close_popup();
-                                                });
-                                            }
-                                        });
-                                    }
-                                });
-                            }
+                        for (key, rows) in groups {
+                            show_group(ui, key, rows);
                         }
                     }
                 });
-            },
-        );
+            })
+            .response;
 
         let mut response = ui.interact(
             text_edit_response.rect,
-            id,
+            popup.id(),
             Sense::focusable_noninteractive(),
         );
-        if selection.is_some() {
+        if selection.is_some() || created.is_some() {
             response.mark_changed();
         }
-        (response, selection)
+        ui.data_mut(|data| {
+            data.insert_temp(response.id.with(DANGLING_KEY_ID), dangling_key.is_some())
+        });
+        (response, selection, created)
     }
 
-    pub fn show<M, I, DD>(
+    pub fn show<M, DD>(
         self,
         ui: &mut Ui,
         model: &M,
         decoration_dependencies: &DD,
         key: &mut Option<String>,
     ) -> Response
+    where
+        M: KeyedListModel<I>,
+        I: KeyedViewItem<DecorationDependencies = DD>,
+    {
+        self.show_navigable(ui, model, decoration_dependencies, key, None)
+            .0
+    }
+
+    /// Like [ModelDropDown::show], but with an entry which creates an item from the search text when no key matches it.
+    /// The key is sanitized with the policy, and `new_item` receives it to create the item, which is added and selected.
+    pub fn show_creating<M, DD>(
+        self,
+        ui: &mut Ui,
+        model: &mut M,
+        decoration_dependencies: &DD,
+        key: &mut Option<String>,
+        policy: &KeyPolicy,
+        new_item: impl FnOnce(String) -> I,
+    ) -> Response
+    where
+        M: KeyedListModel<I>,
+        I: KeyedViewItem<DecorationDependencies = DD>,
+    {
+        let create_key = |search: &str| {
+            let key = policy.sanitize(search.trim());
+            (!key.is_empty() && !model.contains(&key)).then_some(key)
+        };
+        let (response, created) =
+            self.show_navigable(ui, &*model, decoration_dependencies, key, Some(&create_key));
+        if let Some(created) = created {
+            let mut item = new_item(created.clone());
+            item.set_key(created);
+            model.add(item);
+        }
+        response
+    }
+
+    /// Show the keyed drop down, followed by the go to definition button if enabled.
+    /// Also returns the key of the item to create, if the create entry was clicked.
+    fn show_navigable<M, DD>(
+        &self,
+        ui: &mut Ui,
+        model: &M,
+        decoration_dependencies: &DD,
+        key: &mut Option<String>,
+        create_key: Option<CreateKey>,
+    ) -> (Response, Option<String>)
+    where
+        M: KeyedListModel<I>,
+        I: KeyedViewItem<DecorationDependencies = DD>,
+    {
+        match self.navigation_model_id {
+            Some(model_id) => {
+                ui.horizontal(|ui| {
+                    let shown =
+                        self.show_keyed(ui, model, decoration_dependencies, key, create_key);
+                    let target = key
+                        .as_deref()
+                        .map(|key| match self.key_transform {
+                            Some(transform) => transform(key),
+                            None => key.to_string(),
+                        })
+                        .filter(|key| model.contains(key));
+                    let button_response = ui
+                        .add_enabled(target.is_some(), Button::new("➡").small())
+                        .on_hover_text("Go to definition");
+                    if let (true, Some(target)) = (button_response.clicked(), target) {
+                        emit_event(
+                            ui.ctx(),
+                            NavigationEvent {
+                                model_id: model_id.to_string(),
+                                key: target,
+                            },
+                        );
+                    }
+                    shown
+                })
+                .inner
+            }
+            None => self.show_keyed(ui, model, decoration_dependencies, key, create_key),
+        }
+    }
+
+    fn show_keyed<M, DD>(
+        &self,
+        ui: &mut Ui,
+        model: &M,
+        decoration_dependencies: &DD,
+        key: &mut Option<String>,
+        create_key: Option<CreateKey>,
+    ) -> (Response, Option<String>)
     where
         M: KeyedListModel<I>,
         I: KeyedViewItem<DecorationDependencies = DD>,
@@ -233,19 +563,41 @@ impl<'a> ModelDropDown<'a> {
             None => model.index_of(key),
         });
 
-        let (response, selection) = self.show_impl(ui, model, decoration_dependencies, index);
-        if let Some(i) = selection {
-            if let Some(new_key) = model.item(i).map(|item| item.key()) {
-                *key = Some(match self.key_reverse_transform {
-                    Some(transform) => transform(&new_key),
-                    None => new_key.to_string(),
-                });
+        let dangling_key = match index {
+            Some(_) => None,
+            None => key.as_deref(),
+        };
+        let (response, selection, created) = self.show_impl(
+            ui,
+            model,
+            decoration_dependencies,
+            index,
+            dangling_key,
+            create_key,
+            I::keyed_stable_id,
+        );
+        if let Some(created) = &created {
+            *key = Some(match self.key_reverse_transform {
+                Some(transform) => transform(created),
+                None => created.clone(),
+            });
+        }
+        match selection {
+            Some(Some(i)) => {
+                if let Some(new_key) = model.item(i).map(|item| item.key()) {
+                    *key = Some(match self.key_reverse_transform {
+                        Some(transform) => transform(&new_key),
+                        None => new_key.to_string(),
+                    });
+                }
             }
+            Some(None) => *key = None,
+            None => {}
         }
-        response
+        (response, created)
     }
 
-    pub fn show_indexed<M, I, DD>(
+    pub fn show_indexed<M, DD>(
         self,
         ui: &mut Ui,
         model: &M,
@@ -256,9 +608,78 @@ impl<'a> ModelDropDown<'a> {
         M: ListModel<I>,
         I: ViewItem<DecorationDependencies = DD>,
     {
-        let (response, selection) = self.show_impl(ui, model, decoration_dependencies, *index);
-        if let Some(i) = selection {
-            *index = Some(i);
+        let (response, selection, _) = self.show_impl(
+            ui,
+            model,
+            decoration_dependencies,
+            *index,
+            None,
+            None,
+            I::stable_id,
+        );
+        if let Some(selection) = selection {
+            *index = selection;
+        }
+        response
+    }
+
+    /// Show a drop down which toggles the checked state of checkable items (see [ViewItem::is_checked]).
+    /// Rows show a check box in the [RowLayout]'s check slot. The field lists the checked items, and the clear entry
+    /// unchecks every item.
+    /// The popup stays open after each toggle unless a close behavior is set.
+    pub fn show_checked<M, DD>(
+        mut self,
+        ui: &mut Ui,
+        model: &mut M,
+        decoration_dependencies: &DD,
+    ) -> Response
+    where
+        M: ListModel<I>,
+        I: ViewItem<DecorationDependencies = DD>,
+    {
+        self.checklist = true;
+        self.close_behavior = self
+            .close_behavior
+            .or(Some(PopupCloseBehavior::CloseOnClickOutside));
+        let (response, selection, _) = self.show_impl(
+            ui,
+            &*model,
+            decoration_dependencies,
+            None,
+            None,
+            None,
+            I::stable_id,
+        );
+        match selection {
+            Some(Some(i)) => {
+                if let Some(item) = model.item_mut(i) {
+                    let checked = item.is_checked().unwrap_or_default();
+                    item.set_checked(!checked);
+                }
+            }
+            Some(None) => model.for_each_mut(|_, item| item.set_checked(false)),
+            None => {}
+        }
+        response
+    }
+
+    /// Show the drop down bound to the current row of a [SelectionModel].
+    /// Selecting an item replaces the selection.
+    pub fn show_selection<M, DD>(
+        self,
+        ui: &mut Ui,
+        model: &M,
+        decoration_dependencies: &DD,
+        selection: &mut SelectionModel,
+    ) -> Response
+    where
+        M: ListModel<I>,
+        I: ViewItem<DecorationDependencies = DD>,
+    {
+        let mut index = selection.current();
+        let response = self.show_indexed(ui, model, decoration_dependencies, &mut index);
+        if response.changed() {
+            selection.set_current(index);
         }
         response
     }
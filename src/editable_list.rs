@@ -1,35 +1,2029 @@
-use egui::{Response, Ui, Widget};
+use std::any::Any;
+use std::borrow::Cow;
+#[cfg(feature = "serde")]
+use std::collections::HashSet;
+use std::hash::Hash;
+use std::sync::Arc;
+
+use egui::epaint::RectShape;
+use egui::{
+    Align, Align2, Checkbox, Color32, Context, Direction, DragAndDrop, Event, EventFilter, Frame,
+    Id, Image, InnerResponse, Key, KeyboardShortcut, Layout, Margin, Modifiers, Rect, Response,
+    RichText, ScrollArea, Sense, Shape, Stroke, TextEdit, TextStyle, Ui, Vec2, Visuals, Widget,
+    Window,
+};
+use indexmap::IndexMap;
+
+use crate::row_layout::{item_badge_pill, modified_marker};
+use crate::validation::validation_badge;
+use crate::{
+    blank_slate_with_action, filter_bar, is_group_collapsed, persisted_group_header,
+    set_group_collapsed, DecorationKind, FilterProxyBuilder, KeyPolicy, KeyedListModel,
+    KeyedViewItem, ListModel, ModelError, ModelEvent, RenameError, RowLayout, SelectionModel,
+    ValidatedItem, ValidationError, ViewItem,
+};
+
+type ItemFn<'a, T, R> = Box<dyn Fn(&T) -> R + 'a>;
+type ItemSetter<'a, T, V> = Box<dyn Fn(&mut T, V) + 'a>;
+type ItemRenderer<'a, T> = Box<dyn Fn(usize, &mut T, &mut Ui) -> Response + 'a>;
+type DragSource<'a, T> = Box<dyn Fn(&mut Ui, Id, usize, &T) + 'a>;
+type NewItem<'a, T> = Box<dyn Fn() -> T + 'a>;
+type ItemDecoration<'a, T> = Box<dyn Fn(&T, &mut Ui) + 'a>;
+type MenuEntries<'a, T> = Box<dyn Fn(&mut Ui, usize, &mut T) -> bool + 'a>;
+type DuplicateItem<'a, M, T> = Box<dyn Fn(&M, usize) -> Option<T> + 'a>;
+type CopyItems<'a, T> = Box<dyn Fn(&[&T]) -> Option<String> + 'a>;
+type PasteItems<'a, M, T> = Box<dyn Fn(&str, &M) -> Option<Vec<T>> + 'a>;
+type SearchRows<'a, M> = Box<dyn FnMut(&mut Ui, &M, bool) -> Vec<usize> + 'a>;
+type RenameItem<'a, M> = Box<dyn Fn(&mut M, usize, &str) -> Result<bool, RenameError> + 'a>;
 
 pub fn editable_list<'a, T: Default>(
     value: &'a mut Vec<T>,
     item_renderer: impl Fn(usize, &mut T, &mut Ui) -> Response + 'a,
 ) -> impl Widget + 'a {
-    move |ui: &mut Ui| {
+    EditableList::new(value, item_renderer)
+}
+
+/// An [editable_list] whose rows use the height, text width, subtitle, badge, and trailing slots of a [RowLayout].
+pub fn editable_list_with_layout<'a, T: Default>(
+    value: &'a mut Vec<T>,
+    row_layout: RowLayout<'a>,
+    item_renderer: impl Fn(usize, &mut T, &mut Ui) -> Response + 'a,
+) -> impl Widget + 'a {
+    EditableList::new(value, item_renderer).row_layout(row_layout)
+}
+
+/// An [editable_list_with_layout] which shows each item's [ViewItem::with_subtitle]
+/// when the [RowLayout] doesn't provide a subtitle, and its [ViewItem::with_tooltip] on hover.
+/// Items with a [ViewItem::group_key] are shown under collapsible group headers,
+/// and checkable items (see [ViewItem::is_checked]) get a leading checkbox.
+pub fn editable_item_list<'a, T: ViewItem + Default>(
+    value: &'a mut Vec<T>,
+    row_layout: RowLayout<'a>,
+    item_renderer: impl Fn(usize, &mut T, &mut Ui) -> Response + 'a,
+) -> impl Widget + 'a {
+    EditableList::new_item_list(value, item_renderer).row_layout(row_layout)
+}
+
+/// An [editable_item_list] which shows a warning badge on items that fail [ValidatedItem::validate].
+pub fn validated_item_list<'a, T: ViewItem + ValidatedItem + Default>(
+    value: &'a mut Vec<T>,
+    row_layout: RowLayout<'a>,
+    item_renderer: impl Fn(usize, &mut T, &mut Ui) -> Response + 'a,
+) -> impl Widget + 'a {
+    EditableList::new_validated_item_list(value, item_renderer).row_layout(row_layout)
+}
+
+/// Per item information shown by lists of items that implement some of the item traits.
+struct ItemHooks<'a, T> {
+    subtitle: ItemFn<'a, T, Option<String>>,
+    tooltip: ItemFn<'a, T, Option<String>>,
+    group: ItemFn<'a, T, Option<String>>,
+    errors: ItemFn<'a, T, Vec<ValidationError>>,
+    checked: ItemFn<'a, T, Option<bool>>,
+    set_checked: ItemSetter<'a, T, bool>,
+    stable_id: ItemFn<'a, T, Option<Id>>,
+    badge: ItemFn<'a, T, Option<String>>,
+    name: ItemFn<'a, T, Option<String>>,
+    decoration: Option<ItemDecoration<'a, T>>,
+}
+
+impl<T> Default for ItemHooks<'_, T> {
+    fn default() -> Self {
+        Self {
+            subtitle: Box::new(|_| None),
+            tooltip: Box::new(|_| None),
+            group: Box::new(|_| None),
+            errors: Box::new(|_| vec![]),
+            checked: Box::new(|_| None),
+            set_checked: Box::new(|_, _| {}),
+            stable_id: Box::new(|_| None),
+            badge: Box::new(|_| None),
+            name: Box::new(|_| None),
+            decoration: None,
+        }
+    }
+}
+
+impl<'a, T: ViewItem + 'a> ItemHooks<'a, T> {
+    /// Replace the hooks [ViewItem] provides, keeping the errors and decoration.
+    fn use_view_item(&mut self) {
+        self.subtitle =
+            Box::new(|item| item.with_subtitle(|subtitle| subtitle.map(str::to_string)));
+        self.tooltip = Box::new(|item| item.with_tooltip(|tooltip| tooltip.map(str::to_string)));
+        self.group = Box::new(|item| item.group_key().map(Cow::into_owned));
+        self.checked = Box::new(T::is_checked);
+        self.set_checked = Box::new(T::set_checked);
+        self.stable_id = Box::new(T::stable_id);
+        self.badge = Box::new(|item| item.badge().map(Cow::into_owned));
+        self.name = Box::new(|item| Some(item.with_text(str::to_string)));
+    }
+}
+
+/// Reads and renames item keys for inline renames (see [EditableList::renamable]).
+struct KeyHooks<'a, M, T> {
+    key: ItemFn<'a, T, String>,
+    /// Renames the row to the key from the text. Returns whether the key changed.
+    rename: RenameItem<'a, M>,
+}
+
+/// A structural edit requested from a row.
+#[derive(Clone, Copy)]
+enum RowAction {
+    InsertAbove,
+    InsertBelow,
+    Duplicate,
+    Rename,
+    Delete,
+    MoveToTop,
+    MoveToBottom,
+    MoveUp,
+    MoveDown,
+}
+
+/// Keyboard shortcuts for an [EditableList], applied to the selected rows while the list has focus.
+/// Set a shortcut to [None] to disable it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ListShortcuts {
+    pub delete: Option<KeyboardShortcut>,
+    /// Only applies to lists with [EditableList::duplicable].
+    pub duplicate: Option<KeyboardShortcut>,
+    pub move_up: Option<KeyboardShortcut>,
+    pub move_down: Option<KeyboardShortcut>,
+    pub select_all: Option<KeyboardShortcut>,
+}
+
+impl Default for ListShortcuts {
+    /// Delete, ctrl + D, alt + up/down, and ctrl + A.
+    fn default() -> Self {
+        Self {
+            delete: Some(KeyboardShortcut::new(Modifiers::NONE, Key::Delete)),
+            duplicate: Some(KeyboardShortcut::new(Modifiers::COMMAND, Key::D)),
+            move_up: Some(KeyboardShortcut::new(Modifiers::ALT, Key::ArrowUp)),
+            move_down: Some(KeyboardShortcut::new(Modifiers::ALT, Key::ArrowDown)),
+            select_all: Some(KeyboardShortcut::new(Modifiers::COMMAND, Key::A)),
+        }
+    }
+}
+
+impl ListShortcuts {
+    /// No shortcuts.
+    pub fn none() -> Self {
+        Self {
+            delete: None,
+            duplicate: None,
+            move_up: None,
+            move_down: None,
+            select_all: None,
+        }
+    }
+}
+
+/// A line of the list: a group header or an item's row. Headers of collapsed groups are followed by no rows.
+enum ListEntry {
+    Header {
+        key: String,
+        count: usize,
+        expanded: bool,
+    },
+    Row(usize),
+}
+
+/// Colors and spacing for the rows of an [EditableList].
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct ListStyle {
+    /// Fill for every other row. [None] disables striping.
+    pub stripe_fill: Option<Color32>,
+    /// Fill for the row under the pointer. [None] disables hover highlighting.
+    pub hover_fill: Option<Color32>,
+    /// Fill for selected rows. [None] uses the theme's selection color.
+    pub selected_fill: Option<Color32>,
+    pub selected_stroke: Stroke,
+    /// Space around each row's contents. Virtualized lists take it out of their row height.
+    pub row_padding: Vec2,
+}
+
+impl ListStyle {
+    /// Striped rows with hover highlighting in the colors of the current theme.
+    pub fn themed(visuals: &Visuals) -> Self {
+        Self {
+            stripe_fill: Some(visuals.faint_bg_color),
+            hover_fill: Some(visuals.widgets.hovered.weak_bg_fill),
+            ..Default::default()
+        }
+    }
+
+    fn background(&self, ui: &Ui, rect: Rect, striped: bool, selected: bool) -> Shape {
+        if selected {
+            let fill = self.selected_fill.unwrap_or(ui.visuals().selection.bg_fill);
+            return Shape::Rect(RectShape::new(rect, 2., fill, self.selected_stroke));
+        }
+        let hover_fill = self.hover_fill.filter(|_| ui.rect_contains_pointer(rect));
+        let stripe_fill = self.stripe_fill.filter(|_| striped);
+        match hover_fill.or(stripe_fill) {
+            Some(fill) => Shape::rect_filled(rect, 2., fill),
+            None => Shape::Noop,
+        }
+    }
+}
+
+/// What an [EditableList] shows when it has no items.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct EmptyState<'a> {
+    pub text: &'a str,
+    /// Shown large above the text, ex. an emoji.
+    pub icon: Option<&'a str>,
+    /// The text of a button which adds the first item, ex. "Add first item". Hidden in read-only lists.
+    pub add_button: Option<&'a str>,
+}
+
+/// Rows waiting for the user to confirm their removal, kept in egui memory while the dialog is open.
+#[derive(Clone)]
+struct PendingDelete {
+    rows: Vec<usize>,
+    /// The length, generation and row IDs of the list when the rows were picked.
+    /// The rows are stale if any of them change.
+    len: usize,
+    generation: Option<u64>,
+    ids: Vec<Option<Id>>,
+    message: String,
+    dont_ask_again: bool,
+}
+
+impl PendingDelete {
+    /// Whether the rows still hold the items they did when they were picked.
+    fn is_current<T>(&self, model: &impl ListModel<T>, stable_id: &ItemFn<T, Option<Id>>) -> bool {
+        self.len == model.len()
+            && self.generation == model.generation()
+            && self.ids == row_ids(model, stable_id, &self.rows)
+    }
+}
+
+/// A row whose key is being edited, kept in egui memory while its field is open.
+#[derive(Clone)]
+struct PendingRename {
+    row: usize,
+    /// The length of the list when the rename started. The row is stale if it changes.
+    len: usize,
+    text: String,
+    /// Why the last submitted key was rejected.
+    error: Option<String>,
+}
+
+/// The payload of a row dragged by its handle. Lists only reorder rows dragged from themselves,
+/// so rows dragged out with [EditableList::drag_out] can be dropped on other widgets (see [list_item_drop_zone]).
+#[derive(Debug, Clone)]
+pub struct DraggedRow<P = ()> {
+    /// Identifies the list the row was dragged from.
+    pub list_id: Id,
+    pub index: usize,
+    /// The data created by [EditableList::drag_out], ex. the item's key or the serialized item.
+    pub data: P,
+}
+
+/// A drop zone for rows dragged out of an [EditableList] with [EditableList::drag_out].
+/// It is highlighted while a row with data of type `P` is dragged, and returns the row once it's dropped.
+pub fn list_item_drop_zone<P: Any + Send + Sync, R>(
+    ui: &mut Ui,
+    add_contents: impl FnOnce(&mut Ui) -> R,
+) -> (InnerResponse<R>, Option<Arc<DraggedRow<P>>>) {
+    ui.dnd_drop_zone(Frame::default().inner_margin(4.), add_contents)
+}
+
+/// The list and index of the row being dragged, if its data is of type `P`.
+fn dragged_row<P: Any + Send + Sync>(ctx: &Context) -> Option<(Id, usize)> {
+    DragAndDrop::payload::<DraggedRow<P>>(ctx).map(|row| (row.list_id, row.index))
+}
+
+/// A [Vec] of any item type as a [ListModel], so [EditableList::new] and friends work with items that
+/// don't implement [ViewItem]. [ListModel::copy] does nothing since the items may not be [Clone].
+pub struct VecList<'a, T>(pub &'a mut Vec<T>);
+
+impl<T> KeyedListModel<T> for VecList<'_, T>
+where
+    T: KeyedViewItem,
+{
+    fn index_of(&self, key: &str) -> Option<usize> {
+        self.0.iter().position(|item| item.key() == key)
+    }
+}
+
+impl<T> ListModel<T> for VecList<'_, T> {
+    fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    fn item(&self, index: usize) -> Option<&T> {
+        self.0.get(index)
+    }
+
+    fn item_mut(&mut self, index: usize) -> Option<&mut T> {
+        self.0.get_mut(index)
+    }
+
+    fn add(&mut self, item: T) {
+        self.0.push(item);
+    }
+
+    fn insert(&mut self, index: usize, item: T) {
+        if index <= self.0.len() {
+            self.0.insert(index, item);
+        }
+    }
+
+    fn remove(&mut self, index: usize) {
+        if index < self.0.len() {
+            self.0.remove(index);
+        }
+    }
+
+    fn swap_items(&mut self, a: usize, b: usize) {
+        if a < self.0.len() && b < self.0.len() {
+            self.0.swap(a, b);
+        }
+    }
+
+    fn copy(&mut self, _: usize, _: usize) {}
+
+    fn move_item(&mut self, from: usize, to: usize) {
+        if from < to && to < self.0.len() {
+            self.0[from..=to].rotate_left(1);
+        } else if to < from && from < self.0.len() {
+            self.0[to..=from].rotate_right(1);
+        }
+    }
+
+    fn remove_many(&mut self, indices: &[usize]) {
+        let mut indices = indices.to_vec();
+        indices.sort_unstable();
+        let mut index = 0;
+        self.0.retain(|_| {
+            let keep = indices.binary_search(&index).is_err();
+            index += 1;
+            keep
+        });
+    }
+
+    fn row_to_index(&self, row_number: usize) -> Option<usize> {
+        (row_number < self.0.len()).then_some(row_number)
+    }
+
+    fn try_copy(&mut self, _: usize, _: usize) -> Result<(), ModelError> {
+        Err(ModelError::Unsupported("copying items in a VecList"))
+    }
+}
+
+/// A list with buttons for adding and removing items. The free functions ([editable_list] and friends)
+/// cover the common cases. Use this builder directly for the optional features.
+///
+/// The list edits any [ListModel] (see [EditableList::with_model]). The constructors which take a [Vec] wrap it in a [VecList].
+pub struct EditableList<'a, T, M = VecList<'a, T>> {
+    model: M,
+    row_layout: RowLayout<'a>,
+    hooks: ItemHooks<'a, T>,
+    item_renderer: ItemRenderer<'a, T>,
+    new_item: NewItem<'a, T>,
+    templates: Vec<(String, NewItem<'a, T>)>,
+    reorderable: bool,
+    selection: Option<&'a mut SelectionModel>,
+    context_menu: bool,
+    menu_entries: Option<MenuEntries<'a, T>>,
+    duplicate: Option<DuplicateItem<'a, M, T>>,
+    rename: Option<KeyHooks<'a, M, T>>,
+    row_height: Option<f32>,
+    copy_items: Option<CopyItems<'a, T>>,
+    paste_items: Option<PasteItems<'a, M, T>>,
+    shortcuts: ListShortcuts,
+    confirm_delete: bool,
+    search: Option<SearchRows<'a, M>>,
+    check_all: bool,
+    read_only: bool,
+    row_numbers: bool,
+    go_to_row: Option<&'a mut Option<usize>>,
+    last_visible_row: Option<&'a mut Option<usize>>,
+    style: ListStyle,
+    empty_state: Option<EmptyState<'a>>,
+    drag_out: Option<DragSource<'a, T>>,
+    dragged_row: fn(&Context) -> Option<(Id, usize)>,
+    id_source: Option<Id>,
+}
+
+impl<'a, T: Default> EditableList<'a, T> {
+    pub fn new(
+        value: &'a mut Vec<T>,
+        item_renderer: impl Fn(usize, &mut T, &mut Ui) -> Response + 'a,
+    ) -> Self {
+        Self::with_factory(value, T::default, item_renderer)
+    }
+
+    /// A list which shows item information like [editable_item_list].
+    pub fn new_item_list(
+        value: &'a mut Vec<T>,
+        item_renderer: impl Fn(usize, &mut T, &mut Ui) -> Response + 'a,
+    ) -> Self
+    where
+        T: ViewItem + 'a,
+    {
+        Self::new(value, item_renderer).view_item_hooks()
+    }
+
+    /// A list which shows item information and validation errors like [validated_item_list].
+    pub fn new_validated_item_list(
+        value: &'a mut Vec<T>,
+        item_renderer: impl Fn(usize, &mut T, &mut Ui) -> Response + 'a,
+    ) -> Self
+    where
+        T: ViewItem + ValidatedItem + 'a,
+    {
+        Self::new(value, item_renderer).validated_item_hooks()
+    }
+}
+
+impl<'a, T> EditableList<'a, T> {
+    /// A list whose add button (and "Insert above/below") creates items with `new_item`,
+    /// for item types without a [Default] or with a better starting value.
+    pub fn with_factory(
+        value: &'a mut Vec<T>,
+        new_item: impl Fn() -> T + 'a,
+        item_renderer: impl Fn(usize, &mut T, &mut Ui) -> Response + 'a,
+    ) -> Self {
+        Self::with_model(VecList(value), new_item, item_renderer)
+    }
+}
+
+impl<'a, T, M: ListModel<T>> EditableList<'a, T, M> {
+    /// A list of the items in any [ListModel], ex. an [crate::ObservableModel] or `&mut` to one.
+    /// Items are added, removed, and moved with the model's methods, so models which reject a change keep their items.
+    pub fn with_model(
+        model: M,
+        new_item: impl Fn() -> T + 'a,
+        item_renderer: impl Fn(usize, &mut T, &mut Ui) -> Response + 'a,
+    ) -> Self {
+        Self {
+            model,
+            row_layout: RowLayout::default(),
+            hooks: ItemHooks::default(),
+            item_renderer: Box::new(item_renderer),
+            new_item: Box::new(new_item),
+            templates: vec![],
+            reorderable: false,
+            selection: None,
+            context_menu: false,
+            menu_entries: None,
+            duplicate: None,
+            rename: None,
+            row_height: None,
+            copy_items: None,
+            paste_items: None,
+            shortcuts: ListShortcuts::default(),
+            confirm_delete: false,
+            search: None,
+            check_all: false,
+            read_only: false,
+            row_numbers: false,
+            go_to_row: None,
+            last_visible_row: None,
+            style: ListStyle::default(),
+            empty_state: None,
+            drag_out: None,
+            dragged_row: dragged_row::<()>,
+            id_source: None,
+        }
+    }
+
+    /// Create new items with `new_item` instead of the one the list was created with.
+    pub fn on_add(mut self, new_item: impl Fn() -> T + 'a) -> Self {
+        self.new_item = Box::new(new_item);
+        self
+    }
+
+    /// Add an entry to a menu opened by the add button, ex. "From template...".
+    /// Once there are templates, the add button only offers them. "Insert above/below" still use the list's factory.
+    pub fn add_template(mut self, label: impl Into<String>, new_item: impl Fn() -> T + 'a) -> Self {
+        self.templates.push((label.into(), Box::new(new_item)));
+        self
+    }
+
+    /// Identifies the list's state (focus, selection shortcuts, collapsed groups, pending deletes, and drags)
+    /// in egui memory. Defaults to the list's position in the parent [Ui], so set it for lists that come and go.
+    pub fn id_source(mut self, id_source: impl Hash) -> Self {
+        self.id_source = Some(Id::new(id_source));
+        self
+    }
+
+    /// Show item information like [editable_item_list], ex. for lists created with [EditableList::with_factory]
+    /// or [EditableList::with_model].
+    pub fn view_item_hooks(mut self) -> Self
+    where
+        T: ViewItem + 'a,
+    {
+        self.hooks.use_view_item();
+        self
+    }
+
+    /// Like [EditableList::view_item_hooks], but rows without a [ViewItem::stable_id] are identified by their key
+    /// (see [KeyedViewItem::keyed_stable_id]) so their state follows them when they're reordered.
+    pub fn keyed_item_hooks(mut self) -> Self
+    where
+        T: KeyedViewItem + 'a,
+    {
+        self.hooks.use_view_item();
+        self.hooks.stable_id = Box::new(T::keyed_stable_id);
+        self
+    }
+
+    /// Show item information and validation errors like [validated_item_list].
+    pub fn validated_item_hooks(mut self) -> Self
+    where
+        T: ViewItem + ValidatedItem + 'a,
+    {
+        self.hooks.use_view_item();
+        self.hooks.errors = Box::new(T::validate);
+        self
+    }
+
+    pub fn row_layout(mut self, row_layout: RowLayout<'a>) -> Self {
+        self.row_layout = row_layout;
+        self
+    }
+
+    /// Show a drag handle on each row. Dropping a row moves it to the position marked by the insertion line.
+    pub fn reorderable(mut self, reorderable: bool) -> Self {
+        self.reorderable = reorderable;
+        self
+    }
+
+    /// Select rows by clicking them, with ctrl + click and shift + click to select several (see [SelectionModel::handle_click]).
+    /// Removing or dragging a selected row acts on every selected row.
+    pub fn selection(mut self, selection: &'a mut SelectionModel) -> Self {
+        self.selection = Some(selection);
+        self
+    }
+
+    /// Right click a row for common edits: insert above or below, duplicate (see [EditableList::duplicable]),
+    /// delete, and move to the top or bottom. Like the remove button, they act on every selected row.
+    pub fn context_menu(mut self, context_menu: bool) -> Self {
+        self.context_menu = context_menu;
+        self
+    }
+
+    /// Add entries to the end of the context menu, enabling it. The callback receives the row's index and item,
+    /// and returns true if it changed the item. Call [Ui::close_menu] when an entry is clicked.
+    pub fn context_menu_entries(
+        mut self,
+        add_entries: impl Fn(&mut Ui, usize, &mut T) -> bool + 'a,
+    ) -> Self {
+        self.context_menu = true;
+        self.menu_entries = Some(Box::new(add_entries));
+        self
+    }
+
+    /// Show "Duplicate" in the context menu, inserting clones after the original items.
+    pub fn duplicable(mut self) -> Self
+    where
+        T: Clone + 'a,
+    {
+        self.duplicate = Some(Box::new(|model, index| model.item(index).cloned()));
+        self
+    }
+
+    /// Like [EditableList::duplicable], but copies get new keys from the policy so keys stay unique.
+    pub fn keyed_duplicable(mut self, policy: &'a KeyPolicy) -> Self
+    where
+        T: KeyedViewItem + Clone + 'a,
+    {
+        self.duplicate = Some(Box::new(|model, index| {
+            let mut copy = model.item(index)?.clone();
+            let key = policy.make_unique(&copy.key(), |key| {
+                model.iter().any(|item| item.key() == key)
+            });
+            copy.set_key(key);
+            Some(copy)
+        }));
+        self
+    }
+
+    /// Show "Rename" in the context menu, which edits the item's key in place of its row.
+    /// Keys are sanitized with the policy, and keys other items have are rejected.
+    /// Items are renamed with [KeyedListModel::rename_key_with_policy], so keyed collections stay in sync.
+    pub fn renamable(mut self, policy: &'a KeyPolicy) -> Self
+    where
+        M: KeyedListModel<T>,
+        T: KeyedViewItem + Clone + 'a,
+    {
+        self.context_menu = true;
+        self.rename = Some(KeyHooks {
+            key: Box::new(|item| item.key().into_owned()),
+            rename: Box::new(|model, row, text| {
+                let old = model
+                    .item(row)
+                    .map(|item| item.key().into_owned())
+                    .ok_or(RenameError::Rejected)?;
+                model
+                    .rename_key_with_policy(&old, text, policy)
+                    .map(|new| new != old)
+            }),
+        });
+        self
+    }
+
+    /// Give every row a leading checkbox bound to the item, ex. for an "enabled" flag.
+    /// Replaces the checkbox item lists show for [ViewItem::is_checked].
+    pub fn checkable(
+        mut self,
+        is_checked: impl Fn(&T) -> bool + 'a,
+        set_checked: impl Fn(&mut T, bool) + 'a,
+    ) -> Self {
+        self.hooks.checked = Box::new(move |item| Some(is_checked(item)));
+        self.hooks.set_checked = Box::new(set_checked);
+        self
+    }
+
+    /// Show a checkbox next to the add button which checks or unchecks every visible checkable row.
+    /// It's partially checked when only some of them are checked.
+    pub fn check_all(mut self, check_all: bool) -> Self {
+        self.check_all = check_all;
+        self
+    }
+
+    /// Hide the add and remove buttons, drag handles, and context menu, disable checkboxes,
+    /// and ignore the shortcuts and pasting that change the list. Selecting, copying, and scrolling still work.
+    /// The item renderer is still given a mutable item, so it should only show read-only widgets.
+    pub fn read_only(mut self, read_only: bool) -> Self {
+        self.read_only = read_only;
+        self
+    }
+
+    /// Show each row's number (its index + 1) in a gutter, and a "Go to #" field above the list
+    /// which scrolls to and selects the row with the entered number.
+    pub fn row_numbers(mut self, row_numbers: bool) -> Self {
+        self.row_numbers = row_numbers;
+        self
+    }
+
+    /// Scroll to and select the row at this index when it's set, then reset it to [None].
+    /// Groups containing the row are expanded. Rows hidden by the search are only selected.
+    pub fn go_to_row(mut self, go_to_row: &'a mut Option<usize>) -> Self {
+        self.go_to_row = Some(go_to_row);
+        self
+    }
+
+    /// Set to the index of the last row on screen after the list is shown, or [None] if no row is.
+    /// Use it to load more items as the list scrolls, ex. with [crate::InfiniteScroll::load_near].
+    pub fn last_visible_row(mut self, last_visible_row: &'a mut Option<usize>) -> Self {
+        self.last_visible_row = Some(last_visible_row);
+        self
+    }
+
+    /// Change the row colors and padding, ex. [ListStyle::themed] for striped rows.
+    pub fn style(mut self, style: ListStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Let rows be dragged by their handle onto other widgets, ex. another list or a reference field.
+    /// The [DraggedRow] payload carries the data `payload` creates for the item, such as its key.
+    /// Accept it with [list_item_drop_zone]. Reordering still works if the list is [EditableList::reorderable].
+    pub fn drag_out<P: Any + Send + Sync>(mut self, payload: impl Fn(usize, &T) -> P + 'a) -> Self {
+        self.drag_out = Some(Box::new(move |ui, list_id, index, item| {
+            let row = DraggedRow {
+                list_id,
+                index,
+                data: payload(index, item),
+            };
+            ui.dnd_drag_source(ui.id().with("drag_handle"), row, |ui| ui.label("☰"));
+        }));
+        self.dragged_row = dragged_row::<P>;
+        self
+    }
+
+    /// Show a placeholder instead of the rows while the list is empty (see [blank_slate_with_action]).
+    pub fn empty_state(mut self, empty_state: EmptyState<'a>) -> Self {
+        self.empty_state = Some(empty_state);
+        self
+    }
+
+    /// Show each item's [ViewItem::with_decoration] for [DecorationKind::List] as a leading icon.
+    /// Every row gets a fixed width slot, so rows without an icon stay aligned. Does nothing unless the
+    /// item type is [ViewItem::decorated] and the [RowLayout] shows decorations.
+    pub fn decorations(mut self, dependencies: &'a T::DecorationDependencies) -> Self
+    where
+        T: ViewItem + 'a,
+    {
+        if T::decorated(DecorationKind::List) {
+            self.hooks.decoration = Some(Box::new(move |item, ui| {
+                item.with_decoration(dependencies, DecorationKind::List, |image| {
+                    decoration_slot(ui, image)
+                })
+            }));
+        }
+        self
+    }
+
+    /// Show the rows in a scroll area and only lay out the ones in view, for lists with thousands of items.
+    /// Every row and group header is given `row_height`, so content taller than that will overlap.
+    pub fn virtualized(mut self, row_height: f32) -> Self {
+        self.row_height = Some(row_height);
+        self
+    }
+
+    /// Ask before removing items, naming the item or counting the items to remove.
+    /// The dialog has a "don't ask again" checkbox which lasts until the app closes.
+    pub fn confirm_delete(mut self, confirm_delete: bool) -> Self {
+        self.confirm_delete = confirm_delete;
+        self
+    }
+
+    /// Show a [filter_bar] above the list and only show the items the proxy accepts.
+    /// Rows keep their order in the list, and adding, removing, and moving items still act on it.
+    /// The proxy refreshes after the list changes, so edits made elsewhere need [FilterProxyBuilder::request_refresh].
+    pub fn search(mut self, proxy: &'a mut FilterProxyBuilder) -> Self
+    where
+        T: ViewItem + 'static,
+    {
+        self.search = Some(Box::new(|ui, model, requires_refresh| {
+            ui.add(filter_bar(proxy));
+            let model = proxy.model(requires_refresh, model);
+            (0..model.visible_len())
+                .filter_map(|row| model.source_index(row))
+                .collect()
+        }));
+        self
+    }
+
+    /// Change or disable the keyboard shortcuts. They apply while the list has keyboard focus,
+    /// which it takes when a row is clicked, and require a [EditableList::selection].
+    pub fn shortcuts(mut self, shortcuts: ListShortcuts) -> Self {
+        self.shortcuts = shortcuts;
+        self
+    }
+
+    /// Copy the selected items to the system clipboard as JSON with ctrl + C, and paste items with ctrl + V
+    /// after the selection (or at the end). Applies while the list has keyboard focus, which it takes when a row is clicked.
+    /// Copying requires a [EditableList::selection].
+    #[cfg(feature = "serde")]
+    pub fn clipboard(mut self) -> Self
+    where
+        T: serde::Serialize + serde::de::DeserializeOwned,
+    {
+        self.copy_items = Some(Box::new(|items| serde_json::to_string(items).ok()));
+        self.paste_items = Some(Box::new(|text, _| serde_json::from_str(text).ok()));
+        self
+    }
+
+    /// Like [EditableList::clipboard], but pasted items whose keys are taken get new keys from the policy.
+    #[cfg(feature = "serde")]
+    pub fn keyed_clipboard(mut self, policy: &'a KeyPolicy) -> Self
+    where
+        T: KeyedViewItem + serde::Serialize + serde::de::DeserializeOwned,
+    {
+        self.copy_items = Some(Box::new(|items| serde_json::to_string(items).ok()));
+        self.paste_items = Some(Box::new(|text, model| {
+            let mut items: Vec<T> = serde_json::from_str(text).ok()?;
+            let mut keys: HashSet<String> =
+                model.iter().map(|item| item.key().into_owned()).collect();
+            for item in &mut items {
+                let key = policy.make_unique(&item.key(), |key| keys.contains(key));
+                keys.insert(key.clone());
+                item.set_key(key);
+            }
+            Some(items)
+        }));
+        self
+    }
+}
+
+impl<T, M: ListModel<T>> Widget for EditableList<'_, T, M> {
+    fn ui(self, ui: &mut Ui) -> Response {
+        let Self {
+            mut model,
+            row_layout,
+            hooks,
+            item_renderer,
+            new_item,
+            templates,
+            reorderable,
+            mut selection,
+            context_menu,
+            menu_entries,
+            duplicate,
+            rename,
+            row_height,
+            copy_items,
+            paste_items,
+            shortcuts,
+            confirm_delete,
+            mut search,
+            check_all,
+            read_only,
+            row_numbers,
+            mut go_to_row,
+            last_visible_row,
+            style,
+            empty_state,
+            drag_out,
+            dragged_row,
+            id_source,
+        } = self;
+        let (reorderable, context_menu, duplicate, paste_items, check_all, shortcuts) = if read_only
+        {
+            let shortcuts = ListShortcuts {
+                select_all: shortcuts.select_all,
+                ..ListShortcuts::none()
+            };
+            (false, false, None, None, false, shortcuts)
+        } else {
+            (
+                reorderable,
+                context_menu,
+                duplicate,
+                paste_items,
+                check_all,
+                shortcuts,
+            )
+        };
+        let rename = rename.filter(|_| !read_only);
         let mut changed = false;
+        // The id of the vertical Ui below is shared with sibling lists, so each list needs its own.
+        let list_id = match id_source {
+            Some(id_source) => ui.make_persistent_id(id_source),
+            None => ui.next_auto_id(),
+        }
+        .with("editable_list_group");
         let mut response = ui
             .vertical(|ui| {
-                if ui.button("+").clicked() {
-                    value.push(T::default());
-                    changed = true;
-                }
-                let mut flagged_for_removal = None;
-                for (i, item) in value.iter_mut().enumerate() {
+                let focus_id = list_id.with("focus");
+                // Refresh the search after the list changed last frame.
+                let changed_id = list_id.with("changed");
+                let mut visible_rows = match &mut search {
+                    Some(search) => {
+                        let requires_refresh = ui.data_mut(|data| data.remove_temp(changed_id));
+                        let mut rows = search(ui, &model, requires_refresh.unwrap_or_default());
+                        rows.sort_unstable();
+                        rows
+                    }
+                    None => (0..model.len()).collect(),
+                };
+                // Keep groups open while the search hides rows, so no match is hidden in a collapsed group.
+                let force_open = visible_rows.len() < model.len();
+                let mut target = go_to_row.as_mut().and_then(|row| row.take());
+                if !read_only || row_numbers {
                     ui.horizontal(|ui| {
-                        if ui.button("-").clicked() {
-                            flagged_for_removal = Some(i);
-                            changed = true;
+                        if row_numbers {
+                            let len = model.len();
+                            target = go_to_field(ui, list_id.with("go_to"), len).or(target);
+                        }
+                        if read_only {
+                            return;
+                        }
+                        let added = if templates.is_empty() {
+                            ui.button("+").clicked().then(&new_item)
+                        } else {
+                            ui.menu_button("+", |ui| template_menu(ui, &templates))
+                                .inner
+                                .flatten()
+                        };
+                        if let Some(item) = added {
+                            let index = model.len();
+                            if model.try_add(item).is_ok() {
+                                visible_rows.push(index);
+                                changed = true;
+                            }
+                        }
+                        if check_all {
+                            changed |= check_all_checkbox(ui, &mut model, &visible_rows, &hooks);
                         }
-                        changed |= item_renderer(i, item, ui).changed();
                     });
                 }
-                if let Some(index) = flagged_for_removal {
-                    value.remove(index);
+                if let Some(empty_state) = empty_state.filter(|_| model.is_empty()) {
+                    let add_button = empty_state.add_button.filter(|_| !read_only);
+                    if blank_slate_with_action(ui, empty_state.icon, empty_state.text, add_button)
+                        && model.try_add(new_item()).is_ok()
+                    {
+                        visible_rows.push(model.len() - 1);
+                        changed = true;
+                    }
+                }
+                let mut action = None;
+                let mut clicked = None;
+                let mut row_rects = vec![];
+                let rename_id = list_id.with("rename");
+                let field_id = rename_id.with("field");
+                let mut renaming = ui
+                    .data(|data| data.get_temp::<PendingRename>(rename_id))
+                    .filter(|renaming| rename.is_some() && renaming.len == model.len());
+                let mut rename_done = None;
+                let target = target.filter(|row| *row < model.len());
+                if let Some(target) = target {
+                    if let Some(selection) = &mut selection {
+                        selection.select(target);
+                    }
+                    if let Some(key) = model.item(target).and_then(|item| (hooks.group)(item)) {
+                        set_group_collapsed(ui, list_id.with(key), false);
+                    }
+                    ui.memory_mut(|memory| memory.request_focus(focus_id));
+                }
+                let mut groups: IndexMap<Option<String>, Vec<usize>> = IndexMap::new();
+                for &i in &visible_rows {
+                    let group = model.item(i).and_then(|item| (hooks.group)(item));
+                    groups.entry(group).or_default().push(i);
+                }
+                let gutter_width = row_numbers.then(|| {
+                    let digits = model.len().max(1).to_string().len();
+                    let font_id = TextStyle::Monospace.resolve(ui.style());
+                    digits as f32 * ui.fonts(|fonts| fonts.glyph_width(&font_id, '0'))
+                });
+                let margin = Margin::symmetric(style.row_padding.x, style.row_padding.y);
+                let mut show_row = |ui: &mut Ui, i: usize, item: &mut T, striped: bool| {
+                    let row_id = (hooks.stable_id)(item).unwrap_or_else(|| Id::new(i));
+                    ui.push_id(row_id, |ui| {
+                        let background = ui.painter().add(Shape::Noop);
+                        let rect = Frame::none()
+                            .inner_margin(margin)
+                            .show(ui, |ui| {
+                                ui.horizontal(|ui| {
+                                    // Virtualized rows are exactly `row_height` tall, padding included.
+                                    let height = match row_height {
+                                        Some(height) => Some((height - 2. * margin.top).max(0.)),
+                                        None => row_layout.fixed_height(),
+                                    };
+                                    if let Some(height) = height {
+                                        ui.set_height(height);
+                                    }
+                                    if let Some(width) = gutter_width {
+                                        row_number(ui, i, width);
+                                    }
+                                    if let Some(drag_out) = &drag_out {
+                                        drag_out(ui, list_id, i, item);
+                                    } else if reorderable {
+                                        let payload = DraggedRow {
+                                            list_id,
+                                            index: i,
+                                            data: (),
+                                        };
+                                        ui.dnd_drag_source(
+                                            ui.id().with("drag_handle"),
+                                            payload,
+                                            |ui| ui.label("☰"),
+                                        );
+                                    }
+                                    if !read_only && ui.button("-").clicked() {
+                                        action = Some((i, RowAction::Delete));
+                                    }
+                                    if let Some(mut checked) = (hooks.checked)(item) {
+                                        if row_layout
+                                            .show_check(ui, &mut checked, !read_only)
+                                            .changed()
+                                        {
+                                            (hooks.set_checked)(item, checked);
+                                            changed = true;
+                                        }
+                                    }
+                                    if let Some(decoration) = &hooks.decoration {
+                                        if row_layout.shows_decoration() {
+                                            decoration(item, ui);
+                                        }
+                                    }
+                                    match renaming.as_mut().filter(|renaming| renaming.row == i) {
+                                        Some(renaming) => {
+                                            rename_done = rename_field(ui, field_id, renaming)
+                                                .or(rename_done);
+                                        }
+                                        None => {
+                                            let response = match row_layout.text_max_width() {
+                                                Some(max_width) => {
+                                                    ui.scope(|ui| {
+                                                        ui.set_max_width(max_width);
+                                                        item_renderer(i, item, ui)
+                                                    })
+                                                    .inner
+                                                }
+                                                None => item_renderer(i, item, ui),
+                                            };
+                                            changed |= response.changed();
+                                            if let Some(tooltip) = (hooks.tooltip)(item) {
+                                                response.on_hover_text(tooltip);
+                                            }
+                                        }
+                                    }
+                                    if row_layout.is_modified(i) {
+                                        modified_marker(ui);
+                                    }
+                                    let mut errors = row_layout.errors(i);
+                                    errors.extend((hooks.errors)(item));
+                                    validation_badge(ui, &errors);
+                                    let subtitle = row_layout
+                                        .subtitle_text(i)
+                                        .or_else(|| (hooks.subtitle)(item));
+                                    if let Some(subtitle) = subtitle {
+                                        row_layout
+                                            .label(ui, RichText::new(subtitle).small().weak());
+                                    }
+                                    row_layout.show_extras(ui, i);
+                                    if let Some(badge) = (hooks.badge)(item) {
+                                        ui.with_layout(
+                                            Layout::right_to_left(Align::Center),
+                                            |ui| item_badge_pill(ui, &badge),
+                                        );
+                                    }
+                                    if selection.is_some() || context_menu {
+                                        let response = ui.interact_bg(Sense::click());
+                                        if response.clicked() {
+                                            clicked = Some((i, ui.input(|i| i.modifiers)));
+                                        }
+                                        if context_menu {
+                                            // Right clicking an unselected row selects it, so the menu acts on that row.
+                                            if response.secondary_clicked()
+                                                && selection
+                                                    .as_ref()
+                                                    .is_some_and(|s| !s.is_selected(i))
+                                            {
+                                                clicked = Some((i, Modifiers::NONE));
+                                            }
+                                            response.context_menu(|ui| {
+                                                if let Some(row_action) = row_menu(
+                                                    ui,
+                                                    duplicate.is_some(),
+                                                    rename.is_some(),
+                                                ) {
+                                                    action = Some((i, row_action));
+                                                }
+                                                if let Some(menu_entries) = &menu_entries {
+                                                    ui.separator();
+                                                    changed |= menu_entries(ui, i, item);
+                                                }
+                                            });
+                                        }
+                                    }
+                                })
+                            })
+                            .response
+                            .rect;
+                        let selected = selection.as_ref().is_some_and(|s| s.is_selected(i));
+                        let shape = style.background(ui, rect, striped, selected);
+                        ui.painter().set(background, shape);
+                        rect
+                    })
+                    .inner
+                };
+                let entries = list_entries(ui, list_id, force_open, groups);
+                let shown_rows: Vec<usize> = entries
+                    .iter()
+                    .filter_map(|entry| match entry {
+                        ListEntry::Row(i) => Some(*i),
+                        ListEntry::Header { .. } => None,
+                    })
+                    .collect();
+                let mut last_visible = None;
+                // Stripes alternate by position so they stay consistent while scrolling a virtualized list.
+                let mut show_entries = |ui: &mut Ui, entries: &[ListEntry], first: usize| {
+                    let mut expanded = true;
+                    for (position, entry) in (first..).zip(entries) {
+                        match entry {
+                            ListEntry::Header {
+                                key,
+                                count,
+                                expanded: was_expanded,
+                            } => {
+                                expanded = persisted_group_header(
+                                    ui,
+                                    list_id.with(key),
+                                    key,
+                                    *count,
+                                    force_open,
+                                );
+                                if expanded != *was_expanded {
+                                    // The rows of a group expanded this frame aren't in the entries yet.
+                                    ui.ctx().request_repaint();
+                                }
+                            }
+                            ListEntry::Row(i) if expanded => {
+                                let Some(item) = model.item_mut(*i) else {
+                                    continue;
+                                };
+                                let rect = show_row(ui, *i, item, position % 2 == 1);
+                                if target == Some(*i) && row_height.is_none() {
+                                    ui.scroll_to_rect(rect, Some(Align::Center));
+                                }
+                                if ui.clip_rect().intersects(rect) {
+                                    last_visible = last_visible.max(Some(*i));
+                                }
+                                row_rects.push((*i, rect));
+                            }
+                            ListEntry::Row(_) => {}
+                        }
+                    }
+                };
+                match row_height {
+                    Some(row_height) => {
+                        let mut scroll_area = ScrollArea::vertical()
+                            .id_source(list_id)
+                            .auto_shrink([false, false]);
+                        // Rows out of view aren't laid out, so scroll to where the row will be.
+                        let position = target.and_then(|target| {
+                            entries.iter().position(
+                                |entry| matches!(entry, ListEntry::Row(i) if *i == target),
+                            )
+                        });
+                        if let Some(position) = position {
+                            let spacing = ui.spacing().item_spacing.y;
+                            scroll_area = scroll_area
+                                .vertical_scroll_offset(position as f32 * (row_height + spacing));
+                        }
+                        scroll_area.show_rows(ui, row_height, entries.len(), |ui, range| {
+                            show_entries(ui, &entries[range.clone()], range.start)
+                        });
+                    }
+                    None => show_entries(ui, &entries, 0),
+                }
+                if let Some(last_visible_row) = last_visible_row {
+                    *last_visible_row = last_visible;
+                }
+                if let Some((i, modifiers)) = clicked {
+                    ui.memory_mut(|memory| memory.request_focus(focus_id));
+                    if let Some(selection) = &mut selection {
+                        selection.handle_click_visible(i, modifiers, &shown_rows);
+                    }
+                }
+                ui.interact(ui.min_rect(), focus_id, Sense::focusable_noninteractive());
+                if ui.memory(|memory| memory.has_focus(focus_id)) {
+                    // Keep the arrow keys from moving focus to other widgets.
+                    let filter = EventFilter {
+                        vertical_arrows: true,
+                        ..Default::default()
+                    };
+                    ui.memory_mut(|memory| memory.set_focus_lock_filter(focus_id, filter));
+                    if let Some(selection) = &mut selection {
+                        let first = selection.selected().find(|row| shown_rows.contains(row));
+                        if let Some(shortcut_action) = shortcut_action(ui, &shortcuts) {
+                            action = first.map(|row| (row, shortcut_action));
+                        } else if shortcuts
+                            .select_all
+                            .is_some_and(|shortcut| ui.input_mut(|i| i.consume_shortcut(&shortcut)))
+                        {
+                            selection.clear();
+                            for &row in &shown_rows {
+                                selection.toggle(row);
+                            }
+                        }
+                    }
+                    for event in ui.input(|i| i.events.clone()) {
+                        match event {
+                            Event::Copy => {
+                                if let (Some(copy_items), Some(selection)) =
+                                    (&copy_items, &selection)
+                                {
+                                    let items: Vec<&T> = selection
+                                        .selected()
+                                        .filter_map(|row| model.item(row))
+                                        .collect();
+                                    if let Some(text) = copy_items(&items) {
+                                        ui.ctx().output_mut(|output| output.copied_text = text);
+                                    }
+                                }
+                            }
+                            Event::Paste(text) => {
+                                if let Some(items) = paste_items
+                                    .as_ref()
+                                    .and_then(|paste_items| paste_items(&text, &model))
+                                {
+                                    paste_rows(&mut model, selection.as_deref_mut(), items);
+                                    changed = true;
+                                }
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+                if reorderable {
+                    if let Some((from, before)) = drop_target(ui, list_id, dragged_row, &row_rects)
+                    {
+                        let rows = affected_rows(selection.as_deref(), from, &shown_rows);
+                        changed |= move_rows(&mut model, selection.as_deref_mut(), &rows, before);
+                    }
+                }
+                renaming = match (renaming, rename_done, &rename) {
+                    (Some(mut renaming), Some(true), Some(rename)) => {
+                        match (rename.rename)(&mut model, renaming.row, &renaming.text) {
+                            Ok(renamed) => {
+                                changed |= renamed;
+                                None
+                            }
+                            Err(error) => {
+                                renaming.error = Some(error.to_string());
+                                ui.memory_mut(|memory| memory.request_focus(field_id));
+                                Some(renaming)
+                            }
+                        }
+                    }
+                    (renaming, None, _) => renaming,
+                    _ => None,
+                };
+                let mut new_pending = None;
+                if let Some((index, action)) = action {
+                    let rows = affected_rows(selection.as_deref(), index, &shown_rows);
+                    changed |= match action {
+                        RowAction::InsertAbove => {
+                            insert_row(&mut model, selection.as_deref_mut(), rows[0], new_item())
+                        }
+                        RowAction::InsertBelow => {
+                            let below = rows[rows.len() - 1] + 1;
+                            insert_row(&mut model, selection.as_deref_mut(), below, new_item())
+                        }
+                        RowAction::Duplicate => match &duplicate {
+                            Some(duplicate) => {
+                                let mut duplicated = false;
+                                for &row in rows.iter().rev() {
+                                    if let Some(copy) = duplicate(&model, row) {
+                                        duplicated |= insert_row(
+                                            &mut model,
+                                            selection.as_deref_mut(),
+                                            row + 1,
+                                            copy,
+                                        );
+                                    }
+                                }
+                                duplicated
+                            }
+                            None => false,
+                        },
+                        RowAction::Rename => {
+                            renaming =
+                                rename
+                                    .as_ref()
+                                    .zip(model.item(index))
+                                    .map(|(rename, item)| PendingRename {
+                                        row: index,
+                                        len: model.len(),
+                                        text: (rename.key)(item),
+                                        error: None,
+                                    });
+                            ui.memory_mut(|memory| memory.request_focus(field_id));
+                            false
+                        }
+                        RowAction::Delete => {
+                            let skip_id = list_id.with("skip_delete_confirmation");
+                            let skip: bool =
+                                ui.data(|data| data.get_temp(skip_id).unwrap_or_default());
+                            if confirm_delete && !skip {
+                                let message = match rows.as_slice() {
+                                    [row] => {
+                                        match model.item(*row).and_then(|item| (hooks.name)(item)) {
+                                            Some(name) => format!("Delete \"{}\"?", name),
+                                            None => "Delete this item?".to_string(),
+                                        }
+                                    }
+                                    rows => format!("Delete {} items?", rows.len()),
+                                };
+                                new_pending = Some(PendingDelete {
+                                    len: model.len(),
+                                    generation: model.generation(),
+                                    ids: row_ids(&model, &hooks.stable_id, &rows),
+                                    rows,
+                                    message,
+                                    dont_ask_again: false,
+                                });
+                                false
+                            } else {
+                                remove_rows(&mut model, selection.as_deref_mut(), &rows)
+                            }
+                        }
+                        RowAction::MoveToTop => {
+                            move_rows(&mut model, selection.as_deref_mut(), &rows, 0)
+                        }
+                        RowAction::MoveToBottom => {
+                            let len = model.len();
+                            move_rows(&mut model, selection.as_deref_mut(), &rows, len)
+                        }
+                        RowAction::MoveUp => shift_rows(
+                            &mut model,
+                            selection.as_deref_mut(),
+                            &visible_rows,
+                            &rows,
+                            true,
+                        ),
+                        RowAction::MoveDown => shift_rows(
+                            &mut model,
+                            selection.as_deref_mut(),
+                            &visible_rows,
+                            &rows,
+                            false,
+                        ),
+                    };
+                }
+                // Edits made while the dialog is open would shift the rows it's about to remove.
+                let pending_id = list_id.with("pending_delete");
+                ui.data_mut(|data| {
+                    if changed {
+                        data.remove::<PendingDelete>(pending_id);
+                    }
+                    if let Some(pending) = new_pending {
+                        data.insert_temp(pending_id, pending);
+                    }
+                    match renaming.filter(|_| !changed) {
+                        Some(renaming) => data.insert_temp(rename_id, renaming),
+                        None => data.remove::<PendingRename>(rename_id),
+                    }
+                });
+                if let Some(rows) = delete_dialog(ui, list_id, |pending| {
+                    pending.is_current(&model, &hooks.stable_id)
+                }) {
+                    changed |= remove_rows(&mut model, selection, &rows);
+                }
+                if changed && search.is_some() {
+                    ui.data_mut(|data| data.insert_temp(changed_id, true));
                 }
             })
             .response;
         if changed {
-            response.mark_changed()
+            response.mark_changed();
         }
         response
     }
 }
+
+/// Show the confirmation dialog for a pending removal. Returns the rows to remove once confirmed.
+/// The removal is cancelled if the rows no longer hold the same items, ex. after an edit made outside the list.
+fn delete_dialog(
+    ui: &mut Ui,
+    list_id: Id,
+    is_current: impl FnOnce(&PendingDelete) -> bool,
+) -> Option<Vec<usize>> {
+    let pending_id = list_id.with("pending_delete");
+    let mut pending: PendingDelete = ui.data(|data| data.get_temp(pending_id))?;
+    if !is_current(&pending) {
+        ui.data_mut(|data| data.remove::<PendingDelete>(pending_id));
+        return None;
+    }
+    let mut confirmed = false;
+    let mut closed = false;
+    Window::new("Confirm delete")
+        .id(pending_id)
+        .collapsible(false)
+        .resizable(false)
+        .anchor(Align2::CENTER_CENTER, [0., 0.])
+        .show(ui.ctx(), |ui| {
+            ui.label(&pending.message);
+            ui.checkbox(&mut pending.dont_ask_again, "Don't ask again this session");
+            ui.horizontal(|ui| {
+                confirmed = ui.button("Delete").clicked();
+                closed = confirmed || ui.button("Cancel").clicked();
+            });
+        });
+    if !closed {
+        ui.data_mut(|data| data.insert_temp(pending_id, pending));
+        return None;
+    }
+    ui.data_mut(|data| {
+        data.remove::<PendingDelete>(pending_id);
+        if confirmed && pending.dont_ask_again {
+            data.insert_temp(list_id.with("skip_delete_confirmation"), true);
+        }
+    });
+    confirmed.then_some(pending.rows)
+}
+
+/// A text field for jumping to a row by its number. Returns the row's index when Enter is pressed.
+fn go_to_field(ui: &mut Ui, id: Id, len: usize) -> Option<usize> {
+    let text_id = id.with("text");
+    let mut text: String = ui.data_mut(|data| data.get_temp(text_id).unwrap_or_default());
+    let response = ui.add(
+        TextEdit::singleline(&mut text)
+            .id(id)
+            .hint_text("Go to #")
+            .desired_width(60.),
+    );
+    let submitted = response.lost_focus() && ui.input(|i| i.key_pressed(Key::Enter));
+    let row = submitted
+        .then(|| text.trim().parse::<usize>().ok())
+        .flatten()
+        .filter(|number| (1..=len).contains(number))
+        .map(|number| number - 1);
+    if row.is_some() {
+        text.clear();
+    }
+    ui.data_mut(|data| data.insert_temp(text_id, text));
+    row
+}
+
+/// The key field of an inline rename, followed by the last error.
+/// Returns true once enter is pressed, or false if the field lost focus otherwise, ex. from escape.
+fn rename_field(ui: &mut Ui, id: Id, renaming: &mut PendingRename) -> Option<bool> {
+    let response = ui.add(TextEdit::singleline(&mut renaming.text).id(id));
+    if let Some(error) = &renaming.error {
+        ui.colored_label(ui.visuals().error_fg_color, error);
+    }
+    response
+        .lost_focus()
+        .then(|| ui.input(|i| i.key_pressed(Key::Enter)))
+}
+
+/// A right aligned row number in a gutter of the given width.
+fn row_number(ui: &mut Ui, index: usize, width: f32) {
+    let size = Vec2::new(width, ui.spacing().interact_size.y);
+    ui.allocate_ui_with_layout(size, Layout::right_to_left(Align::Center), |ui| {
+        ui.set_min_width(width);
+        ui.label(RichText::new((index + 1).to_string()).monospace().weak());
+    });
+}
+
+/// The entries of the add button's menu. Returns the created item.
+fn template_menu<T>(ui: &mut Ui, templates: &[(String, NewItem<T>)]) -> Option<T> {
+    for (label, new_item) in templates {
+        if ui.button(label).clicked() {
+            ui.close_menu();
+            return Some(new_item());
+        }
+    }
+    None
+}
+
+/// A square slot for a row's icon, the height of a button. Larger images are scaled down to fit.
+fn decoration_slot(ui: &mut Ui, image: Option<Image>) {
+    let size = Vec2::splat(ui.spacing().interact_size.y);
+    let layout = Layout::centered_and_justified(Direction::LeftToRight);
+    ui.allocate_ui_with_layout(size, layout, |ui| {
+        ui.set_min_size(size);
+        if let Some(image) = image {
+            ui.add(image.max_size(size));
+        }
+    });
+}
+
+/// A tri-state checkbox for the checkable items among the rows. Returns true if it changed the items.
+fn check_all_checkbox<T>(
+    ui: &mut Ui,
+    model: &mut impl ListModel<T>,
+    rows: &[usize],
+    hooks: &ItemHooks<T>,
+) -> bool {
+    let states: Vec<bool> = rows
+        .iter()
+        .filter_map(|&i| model.item(i).and_then(|item| (hooks.checked)(item)))
+        .collect();
+    let checked_count = states.iter().filter(|checked| **checked).count();
+    let mut all_checked = !states.is_empty() && checked_count == states.len();
+    let partially_checked = checked_count > 0 && !all_checked;
+    let checkbox = Checkbox::without_text(&mut all_checked).indeterminate(partially_checked);
+    if !ui.add_enabled(!states.is_empty(), checkbox).changed() {
+        return false;
+    }
+    for &i in rows {
+        if let Some(item) = model.item_mut(i) {
+            if (hooks.checked)(item).is_some() {
+                (hooks.set_checked)(item, all_checked);
+            }
+        }
+    }
+    true
+}
+
+/// The row action whose shortcut was pressed, if any.
+fn shortcut_action(ui: &mut Ui, shortcuts: &ListShortcuts) -> Option<RowAction> {
+    [
+        (shortcuts.delete, RowAction::Delete),
+        (shortcuts.duplicate, RowAction::Duplicate),
+        (shortcuts.move_up, RowAction::MoveUp),
+        (shortcuts.move_down, RowAction::MoveDown),
+    ]
+    .into_iter()
+    .find_map(|(shortcut, action)| {
+        let shortcut = shortcut?;
+        ui.input_mut(|i| i.consume_shortcut(&shortcut))
+            .then_some(action)
+    })
+}
+
+/// Lay out the groups as headers followed by their rows, leaving out the rows of collapsed groups.
+fn list_entries(
+    ui: &Ui,
+    list_id: Id,
+    force_open: bool,
+    groups: IndexMap<Option<String>, Vec<usize>>,
+) -> Vec<ListEntry> {
+    let mut entries = vec![];
+    for (key, rows) in groups {
+        if let Some(key) = key {
+            let expanded = force_open || !is_group_collapsed(ui, list_id.with(&key));
+            entries.push(ListEntry::Header {
+                count: rows.len(),
+                key,
+                expanded,
+            });
+            if !expanded {
+                continue;
+            }
+        }
+        entries.extend(rows.into_iter().map(ListEntry::Row));
+    }
+    entries
+}
+
+/// While a row of the list is dragged over it, draw a line where the row would be inserted.
+/// Returns the row's index and the index it's dropped before.
+fn drop_target(
+    ui: &mut Ui,
+    list_id: Id,
+    dragged_row: fn(&Context) -> Option<(Id, usize)>,
+    row_rects: &[(usize, Rect)],
+) -> Option<(usize, usize)> {
+    let (from_list, from) = dragged_row(ui.ctx())?;
+    let pointer = ui.ctx().pointer_interact_pos()?;
+    let (_, last_rect) = row_rects.last()?;
+    let list_rect = row_rects
+        .iter()
+        .fold(*last_rect, |rect, (_, row_rect)| rect.union(*row_rect));
+    if from_list != list_id || !list_rect.contains(pointer) {
+        return None;
+    }
+    // Insert before the first row whose center is below the pointer, or after the last row.
+    let (before, y) = match row_rects
+        .iter()
+        .find(|(_, rect)| pointer.y < rect.center().y)
+    {
+        Some((i, rect)) => (*i, rect.top()),
+        None => (row_rects[row_rects.len() - 1].0 + 1, last_rect.bottom()),
+    };
+    let stroke = Stroke::new(2., ui.visuals().selection.stroke.color);
+    ui.painter().hline(list_rect.x_range(), y, stroke);
+    if !ui.input(|i| i.pointer.any_released()) {
+        return None;
+    }
+    DragAndDrop::clear_payload(ui.ctx());
+    Some((from, before))
+}
+
+/// The rows an operation on the given row applies to: the whole selection if the row is selected, otherwise just the row.
+/// Selected rows which aren't shown are left out so operations never touch items the user can't see.
+fn affected_rows(
+    selection: Option<&SelectionModel>,
+    index: usize,
+    shown_rows: &[usize],
+) -> Vec<usize> {
+    match selection {
+        Some(selection) if selection.is_selected(index) => selection
+            .selected()
+            .filter(|row| shown_rows.contains(row))
+            .collect(),
+        _ => vec![index],
+    }
+}
+
+/// The built-in entries of a row's context menu.
+fn row_menu(ui: &mut Ui, duplicable: bool, renamable: bool) -> Option<RowAction> {
+    let mut entries = vec![
+        ("Insert above", RowAction::InsertAbove),
+        ("Insert below", RowAction::InsertBelow),
+    ];
+    if duplicable {
+        entries.push(("Duplicate", RowAction::Duplicate));
+    }
+    if renamable {
+        entries.push(("Rename", RowAction::Rename));
+    }
+    entries.extend([
+        ("Delete", RowAction::Delete),
+        ("Move to top", RowAction::MoveToTop),
+        ("Move to bottom", RowAction::MoveToBottom),
+    ]);
+    let mut clicked = None;
+    for (text, action) in entries {
+        if ui.button(text).clicked() {
+            clicked = Some(action);
+            ui.close_menu();
+        }
+    }
+    clicked
+}
+
+/// Insert the items after the selection, or at the end without one, and select them.
+fn paste_rows<T>(
+    model: &mut impl ListModel<T>,
+    selection: Option<&mut SelectionModel>,
+    items: Vec<T>,
+) {
+    let Some(selection) = selection else {
+        for item in items {
+            model.add(item);
+        }
+        return;
+    };
+    let start = selection
+        .selected()
+        .filter(|row| *row < model.len())
+        .last()
+        .map_or(model.len(), |row| row + 1);
+    let mut count = 0;
+    for item in items {
+        count += insert_row(model, Some(selection), start + count, item) as usize;
+    }
+    if count > 0 {
+        selection.select(start);
+        selection.extend_to(start + count - 1);
+    }
+}
+
+/// Insert an item, keeping the selection on the same items. Returns false if the model rejected it.
+fn insert_row<T>(
+    model: &mut impl ListModel<T>,
+    selection: Option<&mut SelectionModel>,
+    index: usize,
+    item: T,
+) -> bool {
+    let index = index.min(model.len());
+    if model.try_insert(index, item).is_err() {
+        return false;
+    }
+    if let Some(selection) = selection {
+        selection.apply_event(&ModelEvent::Inserted(index));
+    }
+    true
+}
+
+/// Remove the rows, keeping the selection on the remaining items. Returns false if nothing was removed.
+/// The [ViewItem::stable_id] of each row, used to tell whether rows still hold the same items.
+fn row_ids<T>(
+    model: &impl ListModel<T>,
+    stable_id: &ItemFn<T, Option<Id>>,
+    rows: &[usize],
+) -> Vec<Option<Id>> {
+    rows.iter()
+        .map(|row| model.item(*row).and_then(stable_id))
+        .collect()
+}
+
+fn remove_rows<T>(
+    model: &mut impl ListModel<T>,
+    selection: Option<&mut SelectionModel>,
+    rows: &[usize],
+) -> bool {
+    let len = model.len();
+    let mut rows: Vec<usize> = rows.iter().copied().filter(|row| *row < len).collect();
+    rows.sort_unstable();
+    rows.dedup();
+    model.remove_many(&rows);
+    if let Some(selection) = selection {
+        if model.len() + rows.len() == len {
+            for &index in rows.iter().rev() {
+                selection.apply_event(&ModelEvent::Removed(index));
+            }
+        } else {
+            // The model kept some of the rows, so there's no telling where the selected items went.
+            selection.clear();
+        }
+    }
+    model.len() < len
+}
+
+/// Move the rows (in ascending order) so they sit together, in order, before the item at `before`.
+/// The selection follows the moved items. Returns false if nothing moved.
+fn move_rows<T>(
+    model: &mut impl ListModel<T>,
+    mut selection: Option<&mut SelectionModel>,
+    rows: &[usize],
+    before: usize,
+) -> bool {
+    let mut moved = false;
+    // Rows above the target stack up just above it, so move the nearest first.
+    let mut end = before;
+    for &from in rows.iter().rev().filter(|row| **row < before) {
+        moved |= move_row(model, selection.as_deref_mut(), from, end - 1);
+        end -= 1;
+    }
+    // Rows below the target line up after it. Moving one doesn't shift the rows below it.
+    for (offset, &from) in rows.iter().filter(|row| **row >= before).enumerate() {
+        moved |= move_row(model, selection.as_deref_mut(), from, before + offset);
+    }
+    moved
+}
+
+/// Move each of the rows (in ascending order) past its neighbor in `order`, the visible rows in ascending order.
+/// Rows stop at the ends of the list and don't pass each other. Returns false if nothing moved.
+fn shift_rows<T>(
+    model: &mut impl ListModel<T>,
+    mut selection: Option<&mut SelectionModel>,
+    order: &[usize],
+    rows: &[usize],
+    up: bool,
+) -> bool {
+    let positions: Vec<usize> = rows
+        .iter()
+        .filter_map(|row| order.binary_search(row).ok())
+        .collect();
+    // Moving a row only shifts the rows between it and its neighbor, which aren't moved after it.
+    let mut moved = false;
+    if up {
+        let mut free = 0;
+        for &position in &positions {
+            if position > free {
+                let (from, to) = (order[position], order[position - 1]);
+                moved |= move_row(model, selection.as_deref_mut(), from, to);
+                free = position;
+            } else {
+                free = position + 1;
+            }
+        }
+    } else {
+        let mut free = order.len();
+        for &position in positions.iter().rev() {
+            if position + 1 < free {
+                let (from, to) = (order[position], order[position + 1]);
+                moved |= move_row(model, selection.as_deref_mut(), from, to);
+                free = position + 1;
+            } else {
+                free = position;
+            }
+        }
+    }
+    moved
+}
+
+/// Move an item, keeping the selection on the same items. Returns false if nothing moved.
+fn move_row<T>(
+    model: &mut impl ListModel<T>,
+    selection: Option<&mut SelectionModel>,
+    from: usize,
+    to: usize,
+) -> bool {
+    if from == to || model.try_move_item(from, to).is_err() {
+        return false;
+    }
+    if let Some(selection) = selection {
+        selection.apply_event(&ModelEvent::Moved { from, to });
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn items() -> Vec<char> {
+        vec!['a', 'b', 'c', 'd', 'e', 'f']
+    }
+
+    fn selection(rows: &[usize]) -> SelectionModel {
+        let mut selection = SelectionModel::default();
+        for &row in rows {
+            selection.toggle(row);
+        }
+        selection
+    }
+
+    fn selected(selection: &SelectionModel) -> Vec<usize> {
+        let mut rows: Vec<usize> = selection.selected().collect();
+        rows.sort_unstable();
+        rows
+    }
+
+    #[test]
+    fn move_to_top() {
+        let mut items = items();
+        let mut selection = selection(&[2, 4]);
+        assert!(move_rows(
+            &mut VecList(&mut items),
+            Some(&mut selection),
+            &[2, 4],
+            0
+        ));
+        assert_eq!(items, ['c', 'e', 'a', 'b', 'd', 'f']);
+        assert_eq!(selected(&selection), [0, 1]);
+    }
+
+    #[test]
+    fn move_to_bottom() {
+        let mut items = items();
+        let mut selection = selection(&[0, 2]);
+        assert!(move_rows(
+            &mut VecList(&mut items),
+            Some(&mut selection),
+            &[0, 2],
+            6
+        ));
+        assert_eq!(items, ['b', 'd', 'e', 'f', 'a', 'c']);
+        assert_eq!(selected(&selection), [4, 5]);
+    }
+
+    #[test]
+    fn move_already_in_place() {
+        let mut items = items();
+        assert!(!move_rows(&mut VecList(&mut items), None, &[0, 1], 0));
+        assert!(!move_rows(&mut VecList(&mut items), None, &[4, 5], 6));
+        assert_eq!(items, self::items());
+    }
+
+    #[test]
+    fn drop_rows_from_both_sides() {
+        let mut items = items();
+        let mut selection = selection(&[0, 4]);
+        assert!(move_rows(
+            &mut VecList(&mut items),
+            Some(&mut selection),
+            &[0, 4],
+            3
+        ));
+        assert_eq!(items, ['b', 'c', 'a', 'e', 'd', 'f']);
+        assert_eq!(selected(&selection), [2, 3]);
+    }
+
+    #[test]
+    fn shift_up() {
+        let order: Vec<usize> = (0..6).collect();
+        let mut items = items();
+        let mut selection = selection(&[1, 3]);
+        assert!(shift_rows(
+            &mut VecList(&mut items),
+            Some(&mut selection),
+            &order,
+            &[1, 3],
+            true
+        ));
+        assert_eq!(items, ['b', 'a', 'd', 'c', 'e', 'f']);
+        assert_eq!(selected(&selection), [0, 2]);
+    }
+
+    #[test]
+    fn shift_up_stops_at_top() {
+        let order: Vec<usize> = (0..6).collect();
+        let mut items = items();
+        assert!(shift_rows(
+            &mut VecList(&mut items),
+            None,
+            &order,
+            &[0, 2],
+            true
+        ));
+        assert_eq!(items, ['a', 'c', 'b', 'd', 'e', 'f']);
+        assert!(!shift_rows(
+            &mut VecList(&mut items),
+            None,
+            &order,
+            &[0, 1],
+            true
+        ));
+        assert_eq!(items, ['a', 'c', 'b', 'd', 'e', 'f']);
+    }
+
+    #[test]
+    fn shift_down_stops_at_bottom() {
+        let order: Vec<usize> = (0..6).collect();
+        let mut items = items();
+        let mut selection = selection(&[3, 5]);
+        assert!(shift_rows(
+            &mut VecList(&mut items),
+            Some(&mut selection),
+            &order,
+            &[3, 5],
+            false
+        ));
+        assert_eq!(items, ['a', 'b', 'c', 'e', 'd', 'f']);
+        assert_eq!(selected(&selection), [4, 5]);
+    }
+
+    #[test]
+    fn shift_past_hidden_rows() {
+        // 'b' and 'd' are filtered out.
+        let order = [0, 2, 4, 5];
+        let mut items = items();
+        assert!(shift_rows(
+            &mut VecList(&mut items),
+            None,
+            &order,
+            &[4],
+            true
+        ));
+        assert_eq!(items, ['a', 'b', 'e', 'c', 'd', 'f']);
+
+        let mut items = self::items();
+        assert!(shift_rows(
+            &mut VecList(&mut items),
+            None,
+            &order,
+            &[0],
+            false
+        ));
+        assert_eq!(items, ['b', 'c', 'a', 'd', 'e', 'f']);
+
+        let mut items = self::items();
+        assert!(!shift_rows(
+            &mut VecList(&mut items),
+            None,
+            &order,
+            &[1],
+            true
+        ));
+        assert_eq!(items, self::items());
+    }
+
+    #[test]
+    fn affected_rows_follow_selection() {
+        let shown: Vec<usize> = (0..6).collect();
+        assert_eq!(affected_rows(None, 2, &shown), [2]);
+        let selection = selection(&[1, 3, 7]);
+        let mut rows = affected_rows(Some(&selection), 3, &shown);
+        rows.sort_unstable();
+        assert_eq!(rows, [1, 3]);
+        assert_eq!(affected_rows(Some(&selection), 2, &shown), [2]);
+    }
+
+    #[test]
+    fn shift_click_and_delete_skip_hidden_rows() {
+        let mut items = items();
+        // A filter hides the middle rows.
+        let shown = [0, 1, 4, 5];
+        let mut selection = SelectionModel::default();
+        selection.handle_click_visible(1, Modifiers::NONE, &shown);
+        selection.handle_click_visible(4, Modifiers::SHIFT, &shown);
+        assert_eq!(selected(&selection), [1, 4]);
+        let rows = affected_rows(Some(&selection), 4, &shown);
+        assert!(remove_rows(
+            &mut VecList(&mut items),
+            Some(&mut selection),
+            &rows
+        ));
+        assert_eq!(items, ['a', 'c', 'd', 'f']);
+    }
+
+    #[derive(Clone, Default)]
+    struct Record {
+        key: String,
+    }
+
+    impl ViewItem for Record {
+        type DecorationDependencies = ();
+
+        fn with_text<F, R>(&self, consumer: F) -> R
+        where
+            F: FnOnce(&str) -> R,
+        {
+            consumer(&self.key)
+        }
+    }
+
+    impl KeyedViewItem for Record {
+        fn key(&self) -> Cow<'_, str> {
+            Cow::Borrowed(&self.key)
+        }
+
+        fn set_key(&mut self, key: String) {
+            self.key = key;
+        }
+    }
+
+    fn rename(
+        model: &mut indexmap::IndexMap<String, Record>,
+        policy: &KeyPolicy,
+        row: usize,
+        text: &str,
+    ) -> Result<bool, RenameError> {
+        let EditableList {
+            mut model, rename, ..
+        } = EditableList::with_model(model, Record::default, |_, _, ui| ui.label(""))
+            .renamable(policy);
+        (rename.unwrap().rename)(&mut model, row, text)
+    }
+
+    #[test]
+    fn rename_keyed_row() {
+        let policy = KeyPolicy::default();
+        let mut model = indexmap::IndexMap::new();
+        for key in ["a", "b", "c"] {
+            model.add(Record {
+                key: key.to_string(),
+            });
+        }
+        assert_eq!(rename(&mut model, &policy, 1, "renamed"), Ok(true));
+        assert_eq!(model.index_of("renamed"), Some(1));
+        assert_eq!(model.index_of("b"), None);
+        assert_eq!(model[1].key, "renamed");
+        assert_eq!(rename(&mut model, &policy, 1, "renamed"), Ok(false));
+        assert_eq!(
+            rename(&mut model, &policy, 0, "c"),
+            Err(RenameError::Collision("c".to_string()))
+        );
+        assert_eq!(model.index_of("a"), Some(0));
+    }
+
+    #[test]
+    fn pending_delete_cancelled_when_rows_move() {
+        let stable_id: ItemFn<char, Option<Id>> = Box::new(|item| Some(Id::new(item)));
+        let mut items = items();
+        let pending = PendingDelete {
+            rows: vec![1],
+            len: items.len(),
+            generation: None,
+            ids: row_ids(&VecList(&mut items), &stable_id, &[1]),
+            message: String::new(),
+            dont_ask_again: false,
+        };
+        assert!(pending.is_current(&VecList(&mut items), &stable_id));
+        items.swap(1, 2);
+        assert!(!pending.is_current(&VecList(&mut items), &stable_id));
+        items.swap(1, 2);
+        items.pop();
+        assert!(!pending.is_current(&VecList(&mut items), &stable_id));
+    }
+}
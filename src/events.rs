@@ -0,0 +1,37 @@
+use egui::{Context, Id};
+
+/// Emitted when the user asks to jump to the record referenced by a field.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NavigationEvent {
+    /// Identifies the model containing the record.
+    pub model_id: String,
+    /// The key of the record in the model.
+    pub key: String,
+}
+
+fn event_bus_id() -> Id {
+    Id::new("__thane_event_bus")
+}
+
+/// Queue an event for the application to handle. Events are stored in the context so widgets can emit them
+/// without extra plumbing. Each event type is queued separately.
+pub fn emit_event<T>(ctx: &Context, event: T)
+where
+    T: Clone + Send + Sync + 'static,
+{
+    ctx.data_mut(|data| {
+        data.get_temp_mut_or_default::<Vec<T>>(event_bus_id())
+            .push(event)
+    });
+}
+
+/// Remove and return every queued event of the given type.
+pub fn take_events<T>(ctx: &Context) -> Vec<T>
+where
+    T: Clone + Send + Sync + 'static,
+{
+    ctx.data_mut(|data| {
+        data.remove_temp::<Vec<T>>(event_bus_id())
+            .unwrap_or_default()
+    })
+}
@@ -1,18 +1,185 @@
-use crate::{ListModel, ViewItem};
+use std::any::Any;
+use std::cmp::Ordering;
+use std::collections::HashSet;
+use std::ops::Range;
+#[cfg(not(target_arch = "wasm32"))]
+use std::sync::atomic::{AtomicU64, Ordering as AtomicOrdering};
+#[cfg(not(target_arch = "wasm32"))]
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+#[cfg(not(target_arch = "wasm32"))]
+use std::sync::Arc;
+#[cfg(not(target_arch = "wasm32"))]
+use std::thread;
+use std::time::Duration;
+
+use egui::{Button, Context, InnerResponse, Response, TextEdit, Ui, Widget};
+use indexmap::IndexMap;
+
+use crate::{
+    contains_word, InvalidationHandle, KeyedListModel, KeyedViewItem, ListModel, MatchMode,
+    ModelError, RowMap, SearchTextCache, TextCollator, ViewItem,
+};
+
+type ItemPredicate<I> = Box<dyn Fn(&I) -> bool>;
+type ItemComparator<I> = Box<dyn Fn(&I, &I) -> Ordering>;
+
+/// The name of the filter added by [FilterProxyBuilder::set_whitelist] and [FilterProxyBuilder::set_blacklist].
+pub const KEY_SET_FILTER: &str = "key set";
+
+/// How a [FilterProxyBuilder] combines its filter expression and named filters.
+/// Filters which aren't in use, like an empty filter expression, are left out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FilterCombination {
+    /// Show items which pass every filter.
+    #[default]
+    All,
+    /// Show items which pass at least one filter.
+    Any,
+}
+
+struct RefilterDelay {
+    ctx: Context,
+    delay: f64,
+    last_change: Option<f64>,
+}
+
+/// The text matching settings of a refresh. Kept apart from the builder so it can move to another thread.
+#[derive(Clone)]
+struct TextMatcher {
+    filter_expr: String,
+    query: String,
+    match_mode: MatchMode,
+    case_sensitive: bool,
+    whole_word: bool,
+}
+
+impl TextMatcher {
+    /// Whether the filter expression is the row number of the source index.
+    fn matches_index(&self, index: usize) -> bool {
+        (index + 1).to_string() == self.filter_expr
+    }
+
+    fn score(&self, text: &str) -> Option<u32> {
+        if self.case_sensitive {
+            self.score_prepared(text)
+        } else {
+            self.score_prepared(&text.to_lowercase())
+        }
+    }
+
+    /// Score text which is already lowercased unless matching is case sensitive.
+    fn score_prepared(&self, text: &str) -> Option<u32> {
+        let score = self.match_mode.score(text, &self.query)?;
+        (!self.whole_word || contains_word(text, &self.query)).then_some(score)
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+struct BackgroundFilter {
+    ctx: Context,
+    /// A copy of the source model's text. Only the text of changed items is copied again.
+    texts: Option<Arc<[Arc<str>]>>,
+    latest_job: Arc<AtomicU64>,
+    receiver: Option<Receiver<Vec<Option<u32>>>>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl BackgroundFilter {
+    fn cancel(&mut self) {
+        self.latest_job.fetch_add(1, AtomicOrdering::Relaxed);
+        self.texts = None;
+        self.receiver = None;
+    }
+}
+
+/// The user-facing settings of a [FilterProxyBuilder]. Enable the `serde` feature to save them,
+/// ex. to restore each panel's search in a workspace file.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct FilterConfig {
+    pub filter_expr: String,
+    pub match_mode: MatchMode,
+    pub case_sensitive: bool,
+    pub whole_word: bool,
+    pub negate: bool,
+}
 
 /// A utility for showing filtered data on a UI by generating a "proxy model".
+///
+/// With the `serde` feature, the filter expression and matching options can be saved. A deserialized proxy refreshes on first use.
+/// Use [FilterProxyBuilder::config] to save them without the builder.
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
 pub struct FilterProxyBuilder {
     filter_expr: String,
+    match_mode: MatchMode,
+    case_sensitive: bool,
+    whole_word: bool,
+    negate: bool,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    filters: IndexMap<String, Box<dyn Any>>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    combination: FilterCombination,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    comparator: Option<Box<dyn Any>>,
+    #[cfg_attr(feature = "serde", serde(skip))]
     requires_refresh: bool,
-    proxy_indices: Vec<usize>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    filter_changed: bool,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    applied_filter: Option<String>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    refilter_delay: Option<RefilterDelay>,
+    #[cfg(not(target_arch = "wasm32"))]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    background: Option<BackgroundFilter>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    proxy_indices: RowMap,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    scores: Vec<u32>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    source_len: usize,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    record_match_ranges: bool,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    match_ranges: Vec<Vec<Range<usize>>>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    revision: u64,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    source: InvalidationHandle,
 }
 
 impl Default for FilterProxyBuilder {
     fn default() -> Self {
         Self {
             filter_expr: String::new(),
+            match_mode: MatchMode::default(),
+            case_sensitive: false,
+            whole_word: false,
+            negate: false,
+            filters: IndexMap::new(),
+            combination: FilterCombination::default(),
+            comparator: None,
             requires_refresh: true,
-            proxy_indices: vec![],
+            filter_changed: false,
+            applied_filter: None,
+            refilter_delay: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            background: None,
+            proxy_indices: RowMap::new(),
+            scores: vec![],
+            source_len: 0,
+            record_match_ranges: false,
+            match_ranges: vec![],
+            revision: 0,
+            source: InvalidationHandle::default(),
         }
     }
 }
@@ -26,25 +193,337 @@ impl FilterProxyBuilder {
         // The client might call this without refreshing after a source model change (but they shouldn't).
         // Compare against the source model's length to be safe.
         self.proxy_indices
-            .get(index)
-            .and_then(|real_index| (*real_index < source_model.len()).then_some(*real_index))
+            .source_index(index)
+            .filter(|real_index| *real_index < source_model.len())
     }
 
     /// Retrieve the proxy index of an item from its index in the source model.
     pub fn proxy_index(&self, source_index: usize) -> Option<usize> {
-        self.proxy_indices
-            .iter()
-            .enumerate()
-            .find_map(|(proxy_index, src)| (*src == source_index).then_some(proxy_index))
+        self.proxy_indices.row(source_index)
+    }
+
+    /// The number of rows which passed the filter.
+    pub fn visible_len(&self) -> usize {
+        self.proxy_indices.len()
+    }
+
+    /// The number of items in the source model when the proxy was last refreshed.
+    pub fn source_len(&self) -> usize {
+        self.source_len
+    }
+
+    /// How well the item in the proxy row matched the filter expression. Higher is better.
+    /// See [MatchMode::score].
+    pub fn score(&self, index: usize) -> Option<u32> {
+        self.scores.get(index).copied()
+    }
+
+    /// Where the filter expression matched the text of the item in the proxy row, as byte ranges into
+    /// the text from [ViewItem::with_text]. Empty unless [FilterProxyBuilder::set_record_match_ranges] is enabled.
+    pub fn match_ranges(&self, index: usize) -> &[Range<usize>] {
+        self.match_ranges
+            .get(index)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Record where each visible item matched when refreshing, ex. so widgets can highlight matches.
+    /// Off by default since it runs the matcher a second time for every visible row.
+    pub fn set_record_match_ranges(&mut self, record_match_ranges: bool) {
+        if self.record_match_ranges != record_match_ranges {
+            self.record_match_ranges = record_match_ranges;
+            self.requires_refresh = true;
+        }
+    }
+
+    pub fn match_mode(&self) -> MatchMode {
+        self.match_mode
+    }
+
+    /// Change how items are matched. Modes which rank results also order the proxy's rows by score.
+    pub fn set_match_mode(&mut self, match_mode: MatchMode) {
+        if self.match_mode != match_mode {
+            self.match_mode = match_mode;
+            self.requires_refresh = true;
+        }
+    }
+
+    pub fn case_sensitive(&self) -> bool {
+        self.case_sensitive
+    }
+
+    /// Match case when comparing the filter expression to item text. Off by default.
+    pub fn set_case_sensitive(&mut self, case_sensitive: bool) {
+        if self.case_sensitive != case_sensitive {
+            self.case_sensitive = case_sensitive;
+            self.requires_refresh = true;
+        }
+    }
+
+    pub fn whole_word(&self) -> bool {
+        self.whole_word
+    }
+
+    /// Only match the filter expression against whole words. Fuzzy matching ignores this option.
+    pub fn set_whole_word(&mut self, whole_word: bool) {
+        if self.whole_word != whole_word {
+            self.whole_word = whole_word;
+            self.requires_refresh = true;
+        }
+    }
+
+    pub fn negate(&self) -> bool {
+        self.negate
+    }
+
+    /// Show the items which do NOT match the filter expression, ex. to hide debug entries.
+    /// An empty expression still shows everything. Named filters are not negated.
+    pub fn set_negate(&mut self, negate: bool) {
+        if self.negate != negate {
+            self.negate = negate;
+            self.requires_refresh = true;
+        }
+    }
+
+    /// A copy of the proxy's settings.
+    pub fn config(&self) -> FilterConfig {
+        FilterConfig {
+            filter_expr: self.filter_expr.clone(),
+            match_mode: self.match_mode,
+            case_sensitive: self.case_sensitive,
+            whole_word: self.whole_word,
+            negate: self.negate,
+        }
+    }
+
+    /// Replace every setting at once, ex. when restoring a saved search.
+    pub fn set_config(&mut self, config: FilterConfig) {
+        if self.config() != config {
+            self.filter_expr = config.filter_expr;
+            self.match_mode = config.match_mode;
+            self.case_sensitive = config.case_sensitive;
+            self.whole_word = config.whole_word;
+            self.negate = config.negate;
+            self.requires_refresh = true;
+        }
     }
 
     /// Access this proxy's filter expression. The function must return true if the filter changes.
+    /// If the new expression extends the previous one, only the rows that matched before are checked again.
     pub fn with_filter_expr(&mut self, func: impl FnOnce(&mut String) -> bool) {
         if func(&mut self.filter_expr) {
+            self.filter_changed = true;
+            if let Some(refilter_delay) = &mut self.refilter_delay {
+                refilter_delay.last_change = Some(refilter_delay.ctx.input(|i| i.time));
+            }
+        }
+    }
+
+    /// Wait until the filter expression hasn't changed for the given time before filtering again,
+    /// ex. to keep typing responsive over a large model. The previous rows are shown in the meantime.
+    /// Other changes, like a changed source model, still refresh immediately.
+    pub fn set_refilter_delay(&mut self, ctx: &Context, delay: Duration) {
+        self.refilter_delay = Some(RefilterDelay {
+            ctx: ctx.clone(),
+            delay: delay.as_secs_f64(),
+            last_change: None,
+        });
+    }
+
+    /// Filter as soon as the filter expression changes. This is the default.
+    pub fn clear_refilter_delay(&mut self) {
+        self.refilter_delay = None;
+    }
+
+    /// Whether the proxy's rows don't reflect the current filter expression yet.
+    pub fn is_refilter_pending(&self) -> bool {
+        self.filter_changed
+    }
+
+    /// Match item text on a background thread, for models too large to filter every frame.
+    /// The text is copied when the source model changes, then the proxy keeps its current rows
+    /// until matching finishes and requests a repaint of `ctx`.
+    ///
+    /// Named filters and sorting still run on the UI thread once the text is matched.
+    /// Custom [ViewItem::matches] implementations aren't used. Changes made through the proxy are matched the next time it is built.
+    /// Requires threads, so this isn't available on the web.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn set_background_filtering(&mut self, ctx: &Context) {
+        self.background = Some(BackgroundFilter {
+            ctx: ctx.clone(),
+            texts: None,
+            latest_job: Arc::new(AtomicU64::new(0)),
+            receiver: None,
+        });
+        self.requires_refresh = true;
+    }
+
+    /// Go back to matching on the UI thread. Any unfinished background job is abandoned.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn clear_background_filtering(&mut self) {
+        if let Some(mut background) = self.background.take() {
+            background.cancel();
             self.requires_refresh = true;
         }
     }
 
+    /// Whether a background job is matching text for a newer filter than the current rows show.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn is_filtering(&self) -> bool {
+        self.background
+            .as_ref()
+            .is_some_and(|background| background.receiver.is_some())
+    }
+
+    /// Whether enough time has passed since the filter expression changed to filter again.
+    /// Schedules a repaint for when the delay ends otherwise.
+    fn filter_settled(&self) -> bool {
+        let Some(refilter_delay) = &self.refilter_delay else {
+            return true;
+        };
+        let Some(last_change) = refilter_delay.last_change else {
+            return true;
+        };
+        let elapsed = refilter_delay.ctx.input(|i| i.time) - last_change;
+        if elapsed >= refilter_delay.delay {
+            true
+        } else {
+            let remaining = refilter_delay.delay - elapsed;
+            refilter_delay
+                .ctx
+                .request_repaint_after(Duration::from_secs_f64(remaining.max(0.)));
+            false
+        }
+    }
+
+    /// Clear the filter expression, leaving the named filters in place.
+    pub fn clear_filter_expr(&mut self) {
+        self.with_filter_expr(|filter_expr| {
+            let changed = !filter_expr.is_empty();
+            filter_expr.clear();
+            changed
+        });
+    }
+
+    /// Add or replace a filter which shows items accepted by the predicate, ex. to hide records based on
+    /// application state. Filters are combined with the filter expression according to [FilterProxyBuilder::set_combination].
+    /// The proxy refreshes when a filter is replaced. If the state it reads changes, call [FilterProxyBuilder::request_refresh].
+    ///
+    /// The item type must match the source model's. Building a proxy of another item type panics.
+    pub fn set_filter<I: 'static>(
+        &mut self,
+        name: impl Into<String>,
+        predicate: impl Fn(&I) -> bool + 'static,
+    ) {
+        let predicate: ItemPredicate<I> = Box::new(predicate);
+        self.filters.insert(name.into(), Box::new(predicate));
+        self.requires_refresh = true;
+    }
+
+    /// Remove the filter with the given name. Returns false if there was no such filter.
+    pub fn remove_filter(&mut self, name: &str) -> bool {
+        let removed = self.filters.shift_remove(name).is_some();
+        self.requires_refresh |= removed;
+        removed
+    }
+
+    /// Remove every named filter, leaving the filter expression in place.
+    pub fn clear_filters(&mut self) {
+        if !self.filters.is_empty() {
+            self.filters.clear();
+            self.requires_refresh = true;
+        }
+    }
+
+    pub fn has_filter(&self, name: &str) -> bool {
+        self.filters.contains_key(name)
+    }
+
+    /// The names of the filters in the order they were added.
+    pub fn filter_names(&self) -> impl Iterator<Item = &str> {
+        self.filters.keys().map(String::as_str)
+    }
+
+    /// Show only the items whose keys are in the set, ex. the entries referenced by another file.
+    /// Stored as the named filter [KEY_SET_FILTER], so it combines with the other filters. Replaces any blacklist.
+    pub fn set_whitelist<I>(&mut self, keys: HashSet<String>)
+    where
+        I: KeyedViewItem + 'static,
+    {
+        self.set_filter(KEY_SET_FILTER, move |item: &I| {
+            keys.contains(item.key().as_ref())
+        });
+    }
+
+    /// Hide the items whose keys are in the set. Stored as the named filter [KEY_SET_FILTER]. Replaces any whitelist.
+    pub fn set_blacklist<I>(&mut self, keys: HashSet<String>)
+    where
+        I: KeyedViewItem + 'static,
+    {
+        self.set_filter(KEY_SET_FILTER, move |item: &I| {
+            !keys.contains(item.key().as_ref())
+        });
+    }
+
+    /// Remove the whitelist or blacklist. Returns false if neither was set.
+    pub fn clear_key_set(&mut self) -> bool {
+        self.remove_filter(KEY_SET_FILTER)
+    }
+
+    pub fn combination(&self) -> FilterCombination {
+        self.combination
+    }
+
+    /// Choose whether items must pass every filter or only one of them.
+    pub fn set_combination(&mut self, combination: FilterCombination) {
+        if self.combination != combination {
+            self.combination = combination;
+            self.requires_refresh = true;
+        }
+    }
+
+    /// Order the visible rows with a comparator, saving a separate [crate::SortProxyBuilder] for the common
+    /// "filter then sort" case. Takes priority over ranking by [MatchMode]. Equal items keep their source order.
+    /// Like [FilterProxyBuilder::set_filter], the item type must match the source model's.
+    pub fn set_sort_by<I: 'static>(&mut self, compare: impl Fn(&I, &I) -> Ordering + 'static) {
+        let compare: ItemComparator<I> = Box::new(compare);
+        self.comparator = Some(Box::new(compare));
+        self.requires_refresh = true;
+    }
+
+    /// Order the visible rows by [ViewItem::sort_key]. The collator compares text keys.
+    pub fn set_sort_by_sort_key<I>(&mut self, collator: impl TextCollator + 'static)
+    where
+        I: ViewItem + 'static,
+    {
+        self.set_sort_by(move |a: &I, b: &I| a.sort_key().compare(&b.sort_key(), &collator));
+    }
+
+    /// Stop sorting the visible rows.
+    pub fn clear_sort(&mut self) {
+        if self.comparator.take().is_some() {
+            self.requires_refresh = true;
+        }
+    }
+
+    pub fn is_sorted(&self) -> bool {
+        self.comparator.is_some()
+    }
+
+    /// Shorthand for [FilterProxyBuilder::set_filter] with an empty name.
+    pub fn set_predicate<I: 'static>(&mut self, predicate: impl Fn(&I) -> bool + 'static) {
+        self.set_filter("", predicate);
+    }
+
+    /// Remove the predicate set with [FilterProxyBuilder::set_predicate].
+    pub fn clear_predicate(&mut self) {
+        self.remove_filter("");
+    }
+
+    pub fn has_predicate(&self) -> bool {
+        self.has_filter("")
+    }
+
     /// Request that the filter proxy refresh its indices.
     pub fn request_refresh(&mut self) {
         self.requires_refresh = true;
@@ -54,7 +533,10 @@ impl FilterProxyBuilder {
     /// Will ONLY trigger a refresh when required. There are three cases for this:
     /// * First time building a proxy (detected automatically)
     /// * Filter expression was altered (detected automatically)
-    /// * Source model was changed. YOU must tell the proxy when this happened.
+    /// * Source model was changed outside of the proxy. YOU must tell the proxy when this happened,
+    ///   unless the source reports a [ListModel::generation] (ex. [crate::ObservableModel]).
+    ///
+    /// The view is read only. Use [FilterProxyBuilder::model_mut] to change the source model through the proxy.
     pub fn model<'a, M, I>(
         &'a mut self,
         requires_refresh: bool,
@@ -62,87 +544,849 @@ impl FilterProxyBuilder {
     ) -> FilterProxyModel<'a, M>
     where
         M: ListModel<I>,
-        I: ViewItem,
+        I: ViewItem + 'static,
+    {
+        self.update(requires_refresh, model, None);
+        FilterProxyModel {
+            builder: self,
+            model,
+            source_len: M::len,
+        }
+    }
+
+    /// Like [FilterProxyBuilder::model], but reads item text from a [SearchTextCache]
+    /// so refreshing doesn't rebuild the text of every item.
+    pub fn model_with_cache<'a, M, I>(
+        &'a mut self,
+        requires_refresh: bool,
+        model: &'a M,
+        cache: &SearchTextCache,
+    ) -> FilterProxyModel<'a, M>
+    where
+        M: ListModel<I>,
+        I: ViewItem + 'static,
     {
-        if self.requires_refresh || requires_refresh {
-            self.requires_refresh = false;
-            self.proxy_indices.clear();
-            for i in 0..model.len() {
-                let matches_filter = model
-                    .item(i)
-                    .map(|item| {
-                        let matches_search_by_index = (i + 1).to_string() == self.filter_expr;
-                        let lowercase_filter_expr = self.filter_expr.to_lowercase();
-                        let matches_search_by_name = item.with_text(|text| {
-                            text.to_lowercase().contains(&lowercase_filter_expr)
-                        });
-                        matches_search_by_index || matches_search_by_name
+        self.update(requires_refresh, model, Some(cache));
+        FilterProxyModel {
+            builder: self,
+            model,
+            source_len: M::len,
+        }
+    }
+
+    /// Like [FilterProxyBuilder::model], but changes made through the view are forwarded to the source model.
+    /// See [FilterProxyModelMut] for how its rows follow them.
+    pub fn model_mut<'a, M, I>(
+        &'a mut self,
+        requires_refresh: bool,
+        model: &'a mut M,
+    ) -> FilterProxyModelMut<'a, M>
+    where
+        M: ListModel<I>,
+        I: ViewItem + 'static,
+    {
+        self.update(requires_refresh, model, None);
+        FilterProxyModelMut {
+            builder: self,
+            model,
+            source_len: M::len,
+        }
+    }
+
+    fn update<M, I>(&mut self, requires_refresh: bool, model: &M, cache: Option<&SearchTextCache>)
+    where
+        M: ListModel<I>,
+        I: ViewItem + 'static,
+    {
+        let source_changed = self.source.update(model);
+        let full_refresh = self.requires_refresh || requires_refresh || source_changed;
+        let refresh = full_refresh || (self.filter_changed && self.filter_settled());
+        #[cfg(not(target_arch = "wasm32"))]
+        if self.background.is_some() {
+            if refresh {
+                self.start_background_job(model, cache, full_refresh);
+            }
+            self.receive_background_job(model, cache);
+            return;
+        }
+        if refresh {
+            self.refresh_from(model, cache, full_refresh);
+        }
+    }
+
+    fn refresh_from<M, I>(&mut self, model: &M, cache: Option<&SearchTextCache>, full_refresh: bool)
+    where
+        M: ListModel<I>,
+        I: ViewItem + 'static,
+    {
+        let candidates = if full_refresh {
+            None
+        } else {
+            self.narrowed_rows(model.len())
+        };
+        let candidates = candidates.unwrap_or_else(|| (0..model.len()).collect());
+        self.mark_refreshed(model.len());
+        let matcher = self.text_matcher();
+        let whole_word = self.whole_word;
+        self.apply_text_scores(model, candidates, |i| {
+            if matcher.matches_index(i) {
+                return Some(u32::MAX);
+            }
+            let item = model.item(i)?;
+            let cached = cache.and_then(|cache| {
+                if matcher.case_sensitive {
+                    cache.text(model, i)
+                } else {
+                    cache.lowercase(model, i)
+                }
+            });
+            let text_score = match cached {
+                Some(text) => matcher.score_prepared(text),
+                None => item.with_text(|text| matcher.score(text)),
+            };
+            text_score.or_else(|| (!whole_word && item.matches(&matcher.query)).then_some(0))
+        });
+        self.update_match_ranges(model, cache);
+    }
+
+    /// Copy the item text if needed and match it on a new thread. The current rows are kept until it finishes.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn start_background_job<M, I>(
+        &mut self,
+        model: &M,
+        cache: Option<&SearchTextCache>,
+        full_refresh: bool,
+    ) where
+        M: ListModel<I>,
+        I: ViewItem + 'static,
+    {
+        self.mark_refreshed(model.len());
+        let matcher = self.text_matcher();
+        let Some(background) = &mut self.background else {
+            return;
+        };
+        let texts = match &background.texts {
+            Some(texts) if !full_refresh && texts.len() == model.len() => texts.clone(),
+            previous => {
+                // Share the copies of unchanged text, so only edited and new items are copied.
+                let previous = previous.as_deref().unwrap_or_default();
+                let texts: Arc<[Arc<str>]> = (0..model.len())
+                    .map(|i| {
+                        let copy = |text: &str| match previous.get(i) {
+                            Some(previous) if **previous == *text => previous.clone(),
+                            _ => Arc::from(text),
+                        };
+                        match cache.and_then(|cache| cache.text(model, i)) {
+                            Some(text) => copy(text),
+                            None => model
+                                .item(i)
+                                .map(|item| item.with_text(copy))
+                                .unwrap_or_else(|| Arc::from("")),
+                        }
                     })
-                    .unwrap_or_default();
-                if matches_filter {
-                    self.proxy_indices.push(i);
+                    .collect();
+                background.texts = Some(texts.clone());
+                texts
+            }
+        };
+        let job = background.latest_job.fetch_add(1, AtomicOrdering::Relaxed) + 1;
+        let latest_job = background.latest_job.clone();
+        let (sender, receiver) = mpsc::channel();
+        background.receiver = Some(receiver);
+        let ctx = background.ctx.clone();
+        thread::spawn(move || {
+            let mut scores = Vec::with_capacity(texts.len());
+            for (i, text) in texts.iter().enumerate() {
+                // Give up if a newer job started.
+                if i % 1024 == 0 && latest_job.load(AtomicOrdering::Relaxed) != job {
+                    return;
                 }
+                scores.push(if matcher.matches_index(i) {
+                    Some(u32::MAX)
+                } else {
+                    matcher.score(text)
+                });
             }
+            if sender.send(scores).is_ok() {
+                ctx.request_repaint();
+            }
+        });
+    }
+
+    /// Apply the results of the latest background job if it finished.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn receive_background_job<M, I>(&mut self, model: &M, cache: Option<&SearchTextCache>)
+    where
+        M: ListModel<I>,
+        I: ViewItem + 'static,
+    {
+        let Some(background) = &mut self.background else {
+            return;
+        };
+        let Some(receiver) = &background.receiver else {
+            return;
+        };
+        let scores = match receiver.try_recv() {
+            Ok(scores) => scores,
+            Err(TryRecvError::Empty) => return,
+            Err(TryRecvError::Disconnected) => {
+                background.receiver = None;
+                return;
+            }
+        };
+        background.receiver = None;
+        let candidates = (0..scores.len().min(model.len())).collect();
+        self.apply_text_scores(model, candidates, |i| scores[i]);
+        self.update_match_ranges(model, cache);
+    }
+
+    /// Record that the proxy's rows reflect the current settings and source model.
+    fn mark_refreshed(&mut self, len: usize) {
+        self.requires_refresh = false;
+        self.source_len = len;
+        self.filter_changed = false;
+        if let Some(refilter_delay) = &mut self.refilter_delay {
+            refilter_delay.last_change = None;
         }
+        self.applied_filter = Some(self.filter_expr.clone());
+    }
 
-        FilterProxyModel {
-            proxy_indices: &self.proxy_indices,
-            model,
+    fn text_matcher(&self) -> TextMatcher {
+        TextMatcher {
+            filter_expr: self.filter_expr.clone(),
+            query: if self.case_sensitive {
+                self.filter_expr.clone()
+            } else {
+                self.filter_expr.to_lowercase()
+            },
+            match_mode: self.match_mode,
+            case_sensitive: self.case_sensitive,
+            whole_word: self.whole_word && self.match_mode == MatchMode::Contains,
+        }
+    }
+
+    /// Build the proxy's rows from the text scores of the candidate source indices,
+    /// combined with the named filters and sorted by the comparator or score.
+    fn apply_text_scores<M, I>(
+        &mut self,
+        model: &M,
+        candidates: Vec<usize>,
+        text_score: impl Fn(usize) -> Option<u32>,
+    ) where
+        M: ListModel<I>,
+        I: 'static,
+    {
+        let predicates: Vec<&ItemPredicate<I>> = self
+            .filters
+            .iter()
+            .map(|(name, predicate)| downcast(name, predicate.as_ref()))
+            .collect();
+        let filtering_text = !self.filter_expr.is_empty();
+        let negate = self.negate && filtering_text;
+        let any = self.combination == FilterCombination::Any;
+        let mut matches = vec![];
+        for i in candidates {
+            let passes_filters = (!predicates.is_empty()).then(|| {
+                model.item(i).is_some_and(|item| {
+                    if any {
+                        predicates.iter().any(|p| p(item))
+                    } else {
+                        predicates.iter().all(|p| p(item))
+                    }
+                })
+            });
+            if !any && passes_filters == Some(false) {
+                continue;
+            }
+            let text_score = if negate {
+                text_score(i).is_none().then_some(0)
+            } else {
+                text_score(i)
+            };
+            let score = match passes_filters {
+                Some(passes) if any && filtering_text => text_score.or(passes.then_some(0)),
+                Some(passes) if any => passes.then_some(0),
+                _ => text_score,
+            };
+            if let Some(score) = score {
+                matches.push((i, score));
+            }
+        }
+        // Stable sorts so equal rows keep their source order.
+        if let Some(compare) = &self.comparator {
+            let compare: &ItemComparator<I> = downcast("sort", compare.as_ref());
+            matches.sort_by(|(a, _), (b, _)| match (model.item(*a), model.item(*b)) {
+                (Some(a), Some(b)) => compare(a, b),
+                _ => Ordering::Equal,
+            });
+        } else if self.match_mode.ranks_results() && !negate && filtering_text {
+            matches.sort_by(|(_, a), (_, b)| b.cmp(a));
+        }
+        self.scores = matches.iter().map(|(_, score)| *score).collect();
+        self.proxy_indices = RowMap::from_rows(matches.into_iter().map(|(i, _)| i).collect());
+        self.revision = self.revision.wrapping_add(1);
+    }
+
+    fn update_match_ranges<M, I>(&mut self, model: &M, cache: Option<&SearchTextCache>)
+    where
+        M: ListModel<I>,
+        I: ViewItem + 'static,
+    {
+        self.match_ranges = if self.record_match_ranges && !self.negate {
+            let ranges = |text: &str| {
+                self.match_mode.match_ranges(
+                    text,
+                    &self.filter_expr,
+                    self.case_sensitive,
+                    self.whole_word,
+                )
+            };
+            self.proxy_indices
+                .rows()
+                .iter()
+                .map(|i| match cache.and_then(|cache| cache.text(model, *i)) {
+                    Some(text) => ranges(text),
+                    None => model
+                        .item(*i)
+                        .map(|item| item.with_text(ranges))
+                        .unwrap_or_default(),
+                })
+                .collect()
+        } else {
+            vec![]
+        };
+    }
+
+    /// Follow a change made through a [FilterProxyModelMut] without filtering again. `remap` gives the new
+    /// source index of each row's item, or [None] if the item was removed. Rows keep their scores and match ranges.
+    fn remap_rows(&mut self, remap: impl Fn(usize) -> Option<usize>) {
+        let mut rows = Vec::with_capacity(self.proxy_indices.len());
+        let mut scores = Vec::with_capacity(self.scores.len());
+        let mut match_ranges = Vec::with_capacity(self.match_ranges.len());
+        let mut old_ranges = std::mem::take(&mut self.match_ranges).into_iter();
+        for (row, source_index) in self.proxy_indices.rows().iter().enumerate() {
+            let ranges = old_ranges.next();
+            if let Some(source_index) = remap(*source_index) {
+                rows.push(source_index);
+                scores.extend(self.scores.get(row).copied());
+                match_ranges.extend(ranges);
+            }
         }
+        self.proxy_indices = RowMap::from_rows(rows);
+        self.scores = scores;
+        self.match_ranges = match_ranges;
+        self.mark_dirty();
     }
+
+    /// Show a new source item in the row, or move an existing row there if `from` is given.
+    fn place_row(&mut self, from: Option<usize>, row: usize, source_index: usize) {
+        let mut rows = self.proxy_indices.rows().to_vec();
+        let (score, ranges) = match from {
+            Some(from) if from < rows.len() => {
+                rows.remove(from);
+                let score = (from < self.scores.len()).then(|| self.scores.remove(from));
+                let ranges =
+                    (from < self.match_ranges.len()).then(|| self.match_ranges.remove(from));
+                (score, ranges)
+            }
+            _ => (Some(0), self.record_match_ranges.then(Vec::new)),
+        };
+        let row = row.min(rows.len());
+        rows.insert(row, source_index);
+        if let Some(score) = score {
+            self.scores.insert(row.min(self.scores.len()), score);
+        }
+        if let Some(ranges) = ranges {
+            self.match_ranges
+                .insert(row.min(self.match_ranges.len()), ranges);
+        }
+        self.proxy_indices = RowMap::from_rows(rows);
+        self.mark_dirty();
+    }
+
+    /// The rows no longer reflect the filter, so filter again the next time the proxy is built.
+    fn mark_dirty(&mut self) {
+        self.requires_refresh = true;
+        self.revision = self.revision.wrapping_add(1);
+    }
+
+    /// When the filter expression only grew since the last refresh, nothing outside the current rows can match.
+    /// Returns those rows in source order, plus the row the expression names by number (if any).
+    /// Whole word matching is excluded since extending a word can match text the shorter word did not.
+    fn narrowed_rows(&self, len: usize) -> Option<Vec<usize>> {
+        let applied_filter = self.applied_filter.as_deref()?;
+        // Extending the expression of a negated filter hides fewer items, so it can't be narrowed either.
+        // Neither can the first expression when any filter may pass, since rows hidden by the named filters may match it.
+        let widens = self.negate
+            || (self.combination == FilterCombination::Any
+                && applied_filter.is_empty()
+                && !self.filters.is_empty());
+        if self.whole_word || widens || !self.filter_expr.starts_with(applied_filter) {
+            return None;
+        }
+        let mut rows = self.proxy_indices.rows().to_vec();
+        if let Some(index) = self
+            .filter_expr
+            .parse::<usize>()
+            .ok()
+            .and_then(|row| row.checked_sub(1))
+        {
+            if index < len && self.proxy_indices.row(index).is_none() {
+                rows.push(index);
+            }
+        }
+        rows.sort_unstable();
+        rows.retain(|i| *i < len);
+        Some(rows)
+    }
+}
+
+/// Recover a filter or comparator stored by the builder.
+fn downcast<'a, T: 'static>(name: &str, value: &'a dyn Any) -> &'a T {
+    value.downcast_ref().unwrap_or_else(|| {
+        panic!(
+            "FilterProxyBuilder {:?} was set for a different item type than the source model's",
+            name
+        )
+    })
 }
 
-/// A filtered view of a [ListModel] generated by a [FilterProxyBuilder].
+/// A search field bound to the proxy's filter expression, with a clear button, match option toggles,
+/// and the number of results. Show it before building the proxy so edits apply on the same frame.
+/// The count is from the proxy's last refresh.
+pub fn filter_bar(proxy: &mut FilterProxyBuilder) -> impl Widget + '_ {
+    move |ui: &mut Ui| {
+        let InnerResponse {
+            inner: changed,
+            mut response,
+        } = ui.horizontal(|ui| {
+            let mut changed = false;
+            proxy.with_filter_expr(|filter_expr| {
+                changed = ui
+                    .add(TextEdit::singleline(filter_expr).hint_text("Search..."))
+                    .changed();
+                changed
+            });
+            let has_filter_expr = !proxy.filter_expr.is_empty();
+            if ui
+                .add_enabled(has_filter_expr, Button::new("✖").small())
+                .on_hover_text("Clear")
+                .clicked()
+            {
+                proxy.clear_filter_expr();
+                changed = true;
+            }
+            let mut fuzzy = proxy.match_mode == MatchMode::Fuzzy;
+            if option_toggle(ui, &mut fuzzy, "~", "Fuzzy matching").changed() {
+                proxy.set_match_mode(if fuzzy {
+                    MatchMode::Fuzzy
+                } else {
+                    MatchMode::Contains
+                });
+                changed = true;
+            }
+            changed |= ui.add(filter_options(proxy)).changed();
+            ui.add(filter_summary(proxy.visible_len(), proxy.source_len()));
+            changed
+        });
+        if changed {
+            response.mark_changed();
+        }
+        response
+    }
+}
+
+/// Small toggle buttons for the proxy's case sensitive ("Aa"), whole word ("ab"), and negate ("!") options.
+pub fn filter_options(proxy: &mut FilterProxyBuilder) -> impl Widget + '_ {
+    move |ui: &mut Ui| {
+        let InnerResponse {
+            inner: changed,
+            mut response,
+        } = ui.horizontal(|ui| {
+            let mut case_sensitive = proxy.case_sensitive;
+            let mut whole_word = proxy.whole_word;
+            let mut negate = proxy.negate;
+            let changed = [
+                option_toggle(ui, &mut case_sensitive, "Aa", "Match case"),
+                option_toggle(ui, &mut whole_word, "ab", "Match whole word"),
+                option_toggle(ui, &mut negate, "!", "Exclude matches"),
+            ]
+            .iter()
+            .any(Response::changed);
+            proxy.set_case_sensitive(case_sensitive);
+            proxy.set_whole_word(whole_word);
+            proxy.set_negate(negate);
+            changed
+        });
+        if changed {
+            response.mark_changed();
+        }
+        response
+    }
+}
+
+/// A weak label with the number of results, ex. "Showing 42 of 1,380".
+pub fn filter_summary(visible_len: usize, source_len: usize) -> impl Widget {
+    move |ui: &mut Ui| {
+        let text = if visible_len == source_len {
+            format!("Showing all {}", group_digits(source_len))
+        } else {
+            format!(
+                "Showing {} of {}",
+                group_digits(visible_len),
+                group_digits(source_len)
+            )
+        };
+        ui.weak(text)
+    }
+}
+
+/// Format a count with thousands separators.
+fn group_digits(count: usize) -> String {
+    let digits = count.to_string();
+    let mut grouped = String::with_capacity(digits.len() + digits.len() / 3);
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            grouped.push(',');
+        }
+        grouped.push(digit);
+    }
+    grouped
+}
+
+fn option_toggle(ui: &mut Ui, value: &mut bool, text: &str, hover_text: &str) -> Response {
+    let mut response = ui.selectable_label(*value, text).on_hover_text(hover_text);
+    if response.clicked() {
+        *value = !*value;
+        response.mark_changed();
+    }
+    response
+}
+
+/// A read only filtered view of a [ListModel] generated by [FilterProxyBuilder::model].
+///
+/// Proxies can be layered, ex. filtering a [crate::SortProxyModel]. [ListModel::row_to_index]
+/// resolves through every layer, and each refresh advances [ListModel::generation] so outer proxies rebuild too.
 pub struct FilterProxyModel<'a, M> {
-    proxy_indices: &'a [usize],
+    builder: &'a FilterProxyBuilder,
     model: &'a M,
+    source_len: fn(&M) -> usize,
+}
+
+impl<M> FilterProxyModel<'_, M> {
+    /// How well the item in the row matched the filter expression. Higher is better.
+    pub fn score(&self, index: usize) -> Option<u32> {
+        self.builder.score(index)
+    }
+
+    /// The number of rows which passed the filter. Same as [ListModel::len].
+    pub fn visible_len(&self) -> usize {
+        self.builder.visible_len()
+    }
+
+    /// The number of items in the source model.
+    pub fn source_len(&self) -> usize {
+        (self.source_len)(self.model)
+    }
+
+    /// Where the filter expression matched the item's text. See [FilterProxyBuilder::match_ranges].
+    pub fn match_ranges(&self, index: usize) -> &[Range<usize>] {
+        self.builder.match_ranges(index)
+    }
+
+    /// Retrieve the index of an item in the source model from its row in this proxy.
+    pub fn source_index(&self, index: usize) -> Option<usize> {
+        let len = self.source_len();
+        self.builder
+            .proxy_indices
+            .source_index(index)
+            .filter(|real_index| *real_index < len)
+    }
+
+    /// Retrieve the row of an item from its index in the source model, ex. to sync selection
+    /// with an unfiltered view. Returns [None] if the item is filtered out.
+    pub fn proxy_index(&self, source_index: usize) -> Option<usize> {
+        self.builder.proxy_index(source_index)
+    }
 }
 
 impl<M, I> ListModel<I> for FilterProxyModel<'_, M>
 where
     M: ListModel<I>,
+    I: ViewItem,
 {
     fn is_empty(&self) -> bool {
-        self.proxy_indices.is_empty()
+        self.builder.proxy_indices.is_empty()
     }
 
     fn len(&self) -> usize {
-        self.proxy_indices.len()
+        self.builder.proxy_indices.len()
     }
 
     fn item(&self, index: usize) -> Option<&I> {
-        self.proxy_indices
-            .get(index)
-            .and_then(|source_index| self.model.item(*source_index))
+        self.source_index(index)
+            .and_then(|source_index| self.model.item(source_index))
     }
 
     fn item_mut(&mut self, _: usize) -> Option<&mut I> {
-        unimplemented!("modify the source model instead")
+        None
+    }
+
+    fn add(&mut self, _: I) {}
+
+    fn insert(&mut self, _: usize, _: I) {}
+
+    fn remove(&mut self, _: usize) {}
+
+    fn swap_items(&mut self, _: usize, _: usize) {}
+
+    fn move_item(&mut self, _: usize, _: usize) {}
+
+    fn copy(&mut self, _: usize, _: usize) {}
+
+    fn row_to_index(&self, row_number: usize) -> Option<usize> {
+        self.source_index(row_number)
+            .and_then(|index| self.model.row_to_index(index))
+    }
+
+    fn generation(&self) -> Option<u64> {
+        Some(self.builder.revision)
+    }
+
+    fn try_add(&mut self, _: I) -> Result<(), ModelError> {
+        Err(READ_ONLY)
+    }
+
+    fn try_insert(&mut self, _: usize, _: I) -> Result<(), ModelError> {
+        Err(READ_ONLY)
     }
 
-    fn add(&mut self, _item: I) {
-        unimplemented!("modify the source model instead")
+    fn try_remove(&mut self, _: usize) -> Result<(), ModelError> {
+        Err(READ_ONLY)
     }
 
-    fn insert(&mut self, _: usize, _: I) {
-        unimplemented!("modify the source model instead")
+    fn try_swap_items(&mut self, _: usize, _: usize) -> Result<(), ModelError> {
+        Err(READ_ONLY)
     }
 
-    fn remove(&mut self, _index: usize) {
-        unimplemented!("modify the source model instead")
+    fn try_move_item(&mut self, _: usize, _: usize) -> Result<(), ModelError> {
+        Err(READ_ONLY)
     }
 
-    fn swap_items(&mut self, _: usize, _: usize) {
-        unimplemented!("modify the source model instead")
+    fn try_copy(&mut self, _: usize, _: usize) -> Result<(), ModelError> {
+        Err(READ_ONLY)
+    }
+}
+
+impl<M, I> KeyedListModel<I> for FilterProxyModel<'_, M>
+where
+    M: KeyedListModel<I>,
+    I: KeyedViewItem,
+{
+    /// Retrieve the row of an item from its key. Returns [None] if the item is filtered out.
+    fn index_of(&self, key: &str) -> Option<usize> {
+        self.model
+            .index_of(key)
+            .and_then(|index| self.proxy_index(index))
+    }
+}
+
+const READ_ONLY: ModelError = ModelError::Unsupported("modifying a read only FilterProxyModel");
+
+/// A filtered view of a [ListModel] generated by [FilterProxyBuilder::model_mut].
+/// Mutations are forwarded to the source model.
+///
+/// The rows follow each change without matching any text: removed items disappear, the rest keep
+/// their rows, and added or inserted items are shown where they were placed even if they don't match.
+/// The proxy is filtered once the next time it's built, however many changes were made.
+/// Editing an item with [ListModel::item_mut] doesn't refilter it. Call [FilterProxyBuilder::request_refresh]
+/// if the edit may change whether it matches.
+pub struct FilterProxyModelMut<'a, M> {
+    builder: &'a mut FilterProxyBuilder,
+    model: &'a mut M,
+    source_len: fn(&M) -> usize,
+}
+
+impl<M> FilterProxyModelMut<'_, M> {
+    /// How well the item in the row matched the filter expression. Higher is better.
+    pub fn score(&self, index: usize) -> Option<u32> {
+        self.builder.score(index)
     }
 
-    fn copy(&mut self, _: usize, _: usize) {
-        unimplemented!("modify the source model instead")
+    /// The number of rows which passed the filter. Same as [ListModel::len].
+    pub fn visible_len(&self) -> usize {
+        self.builder.visible_len()
+    }
+
+    /// The number of items in the source model.
+    pub fn source_len(&self) -> usize {
+        (self.source_len)(self.model)
+    }
+
+    /// Where the filter expression matched the item's text. See [FilterProxyBuilder::match_ranges].
+    pub fn match_ranges(&self, index: usize) -> &[Range<usize>] {
+        self.builder.match_ranges(index)
+    }
+
+    /// Retrieve the index of an item in the source model from its row in this proxy.
+    pub fn source_index(&self, index: usize) -> Option<usize> {
+        let len = self.source_len();
+        self.builder
+            .proxy_indices
+            .source_index(index)
+            .filter(|real_index| *real_index < len)
+    }
+
+    /// Retrieve the row of an item from its index in the source model. Returns [None] if the item is filtered out.
+    pub fn proxy_index(&self, source_index: usize) -> Option<usize> {
+        self.builder.proxy_index(source_index)
+    }
+}
+
+impl<M, I> ListModel<I> for FilterProxyModelMut<'_, M>
+where
+    M: ListModel<I>,
+    I: ViewItem + 'static,
+{
+    fn is_empty(&self) -> bool {
+        self.builder.proxy_indices.is_empty()
+    }
+
+    fn len(&self) -> usize {
+        self.builder.proxy_indices.len()
+    }
+
+    fn item(&self, index: usize) -> Option<&I> {
+        self.source_index(index)
+            .and_then(|source_index| self.model.item(source_index))
+    }
+
+    fn item_mut(&mut self, index: usize) -> Option<&mut I> {
+        self.source_index(index)
+            .and_then(|source_index| self.model.item_mut(source_index))
+    }
+
+    /// The item is shown in a new last row until the proxy is filtered again.
+    fn add(&mut self, item: I) {
+        let source_index = self.model.len();
+        self.model.add(item);
+        if self.model.len() > source_index {
+            let row = self.len();
+            self.builder.place_row(None, row, source_index);
+        }
+    }
+
+    /// Inserts the item in the source model before the item at the given row,
+    /// or after the last row if the index is the proxy's length.
+    fn insert(&mut self, index: usize, item: I) {
+        let source_index = if index == self.len() {
+            Some(
+                index
+                    .checked_sub(1)
+                    .and_then(|last| self.source_index(last))
+                    .map_or(self.model.len(), |last| last + 1),
+            )
+        } else {
+            self.source_index(index)
+        };
+        let Some(source_index) = source_index else {
+            return;
+        };
+        let len = self.model.len();
+        self.model.insert(source_index, item);
+        if self.model.len() > len {
+            self.builder
+                .remap_rows(|i| Some(if i >= source_index { i + 1 } else { i }));
+            self.builder.place_row(None, index, source_index);
+        }
+    }
+
+    fn remove(&mut self, index: usize) {
+        self.remove_many(&[index]);
+    }
+
+    fn swap_items(&mut self, a: usize, b: usize) {
+        if let (Some(a), Some(b)) = (self.source_index(a), self.source_index(b)) {
+            self.model.swap_items(a, b);
+            self.builder.mark_dirty();
+        }
+    }
+
+    /// Moves the item next to the one at the target row. Hidden items in between keep their order.
+    fn move_item(&mut self, from: usize, to: usize) {
+        let (Some(source_from), Some(source_to)) = (self.source_index(from), self.source_index(to))
+        else {
+            return;
+        };
+        if from == to {
+            return;
+        }
+        self.model.move_item(source_from, source_to);
+        self.builder.remap_rows(|i| {
+            Some(if i == source_from {
+                source_to
+            } else if source_from < i && i <= source_to {
+                i - 1
+            } else if source_to <= i && i < source_from {
+                i + 1
+            } else {
+                i
+            })
+        });
+        self.builder.place_row(Some(from), to, source_to);
+    }
+
+    fn copy(&mut self, a: usize, b: usize) {
+        if let (Some(a), Some(b)) = (self.source_index(a), self.source_index(b)) {
+            self.model.copy(a, b);
+            self.builder.mark_dirty();
+        }
+    }
+
+    /// Removes the items from the source model in one call, then drops their rows in a single pass.
+    fn remove_many(&mut self, indices: &[usize]) {
+        let mut source_indices: Vec<usize> = indices
+            .iter()
+            .filter_map(|index| self.source_index(*index))
+            .collect();
+        source_indices.sort_unstable();
+        source_indices.dedup();
+        if source_indices.is_empty() {
+            return;
+        }
+        let len = self.model.len();
+        self.model.remove_many(&source_indices);
+        if self.model.len() + source_indices.len() == len {
+            self.builder
+                .remap_rows(|i| match source_indices.binary_search(&i) {
+                    Ok(_) => None,
+                    Err(removed_before) => Some(i - removed_before),
+                });
+        } else {
+            // The source kept some of the items, so there's no telling which rows are still valid.
+            self.builder.refresh_from(&*self.model, None, true);
+        }
     }
 
     fn row_to_index(&self, row_number: usize) -> Option<usize> {
-        self.proxy_indices
-            .get(row_number)
-            .and_then(|index| self.model.row_to_index(*index))
+        self.source_index(row_number)
+            .and_then(|index| self.model.row_to_index(index))
+    }
+
+    fn generation(&self) -> Option<u64> {
+        Some(self.builder.revision)
+    }
+}
+
+impl<M, I> KeyedListModel<I> for FilterProxyModelMut<'_, M>
+where
+    M: KeyedListModel<I>,
+    I: KeyedViewItem + 'static,
+{
+    /// Retrieve the row of an item from its key. Returns [None] if the item is filtered out.
+    fn index_of(&self, key: &str) -> Option<usize> {
+        self.model
+            .index_of(key)
+            .and_then(|index| self.proxy_index(index))
     }
 }
@@ -0,0 +1,116 @@
+use crate::{ListModel, ModelError, TreeModel};
+
+/// A flat view of a [TreeModel] in depth first order so it can be rendered by list widgets.
+/// Each row remembers its path in the tree, which can be used to indent rows by depth.
+///
+/// The model is read only, so modify the source tree instead. Mutations through [ListModel] do nothing,
+/// and the checked ones return [ModelError::Unsupported].
+pub struct FlattenedTreeModel<'a, T> {
+    paths: Vec<Vec<usize>>,
+    tree: &'a T,
+}
+
+impl<'a, T> FlattenedTreeModel<'a, T> {
+    /// Flatten every node in the tree.
+    pub fn new<I>(tree: &'a T) -> Self
+    where
+        T: TreeModel<I>,
+    {
+        Self::with_expanded(tree, |_| true)
+    }
+
+    /// Flatten the tree, skipping the descendants of nodes where `expanded` returns false.
+    pub fn with_expanded<I>(tree: &'a T, expanded: impl Fn(&[usize]) -> bool) -> Self
+    where
+        T: TreeModel<I>,
+    {
+        let mut paths = vec![];
+        let mut stack: Vec<Vec<usize>> = tree.children(&[]).into_iter().rev().collect();
+        while let Some(path) = stack.pop() {
+            if expanded(&path) {
+                stack.extend(tree.children(&path).into_iter().rev());
+            }
+            paths.push(path);
+        }
+        Self { paths, tree }
+    }
+
+    /// Retrieve the path in the tree of the given row.
+    pub fn path(&self, row: usize) -> Option<&[usize]> {
+        self.paths.get(row).map(|path| path.as_slice())
+    }
+
+    /// Retrieve the depth of the given row. Top level nodes have a depth of zero.
+    pub fn depth(&self, row: usize) -> Option<usize> {
+        self.paths.get(row).map(|path| path.len() - 1)
+    }
+
+    /// Retrieve the row of a node from its path in the tree.
+    pub fn row_of(&self, path: &[usize]) -> Option<usize> {
+        self.paths.iter().position(|p| p == path)
+    }
+}
+
+impl<T, I> ListModel<I> for FlattenedTreeModel<'_, T>
+where
+    T: TreeModel<I>,
+{
+    fn is_empty(&self) -> bool {
+        self.paths.is_empty()
+    }
+
+    fn len(&self) -> usize {
+        self.paths.len()
+    }
+
+    fn item(&self, index: usize) -> Option<&I> {
+        self.paths.get(index).and_then(|path| self.tree.item(path))
+    }
+
+    fn item_mut(&mut self, _: usize) -> Option<&mut I> {
+        None
+    }
+
+    fn add(&mut self, _item: I) {}
+
+    fn insert(&mut self, _: usize, _: I) {}
+
+    fn remove(&mut self, _index: usize) {}
+
+    fn swap_items(&mut self, _: usize, _: usize) {}
+
+    fn copy(&mut self, _: usize, _: usize) {}
+
+    fn row_to_index(&self, row_number: usize) -> Option<usize> {
+        (0..self.paths.len())
+            .contains(&row_number)
+            .then_some(row_number)
+    }
+
+    fn try_add(&mut self, _: I) -> Result<(), ModelError> {
+        Err(READ_ONLY)
+    }
+
+    fn try_insert(&mut self, _: usize, _: I) -> Result<(), ModelError> {
+        Err(READ_ONLY)
+    }
+
+    fn try_remove(&mut self, _: usize) -> Result<(), ModelError> {
+        Err(READ_ONLY)
+    }
+
+    fn try_swap_items(&mut self, _: usize, _: usize) -> Result<(), ModelError> {
+        Err(READ_ONLY)
+    }
+
+    fn try_move_item(&mut self, _: usize, _: usize) -> Result<(), ModelError> {
+        Err(READ_ONLY)
+    }
+
+    fn try_copy(&mut self, _: usize, _: usize) -> Result<(), ModelError> {
+        Err(READ_ONLY)
+    }
+}
+
+const READ_ONLY: ModelError =
+    ModelError::Unsupported("modifying a FlattenedTreeModel, modify the source tree instead");
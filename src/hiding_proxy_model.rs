@@ -0,0 +1,155 @@
+use std::collections::HashSet;
+use std::marker::PhantomData;
+
+use crate::{ListModel, RowMap};
+
+type HidePredicate<'a, I> = Box<dyn Fn(usize, &I) -> bool + 'a>;
+
+/// Wraps a [ListModel] and hides some of its items, ex. a "hide completed entries" toggle.
+/// Lighter than a [crate::FilterProxyBuilder] since nothing is matched against text.
+///
+/// Rows are rebuilt after every change made through this model. Call [HidingProxyModel::refresh]
+/// after changing which items are hidden or modifying the source directly.
+pub struct HidingProxyModel<'a, M, I> {
+    model: M,
+    hidden: HidePredicate<'a, I>,
+    rows: RowMap,
+    _item: PhantomData<I>,
+}
+
+impl<'a, M, I> HidingProxyModel<'a, M, I>
+where
+    M: ListModel<I>,
+{
+    /// Hide the items whose source indices are in the set.
+    pub fn new(model: M, hidden: &'a HashSet<usize>) -> Self {
+        Self::with_predicate(model, move |index, _| hidden.contains(&index))
+    }
+
+    /// Hide the items for which the predicate returns true. It receives the item's source index.
+    pub fn with_predicate(model: M, hidden: impl Fn(usize, &I) -> bool + 'a) -> Self {
+        let mut proxy = Self {
+            model,
+            hidden: Box::new(hidden),
+            rows: RowMap::new(),
+            _item: PhantomData,
+        };
+        proxy.refresh();
+        proxy
+    }
+
+    pub fn inner(&self) -> &M {
+        &self.model
+    }
+
+    pub fn into_inner(self) -> M {
+        self.model
+    }
+
+    /// Recompute which items are hidden.
+    pub fn refresh(&mut self) {
+        self.rows.clear();
+        for index in 0..self.model.len() {
+            if let Some(item) = self.model.item(index) {
+                if !(self.hidden)(index, item) {
+                    self.rows.push(index);
+                }
+            }
+        }
+    }
+
+    /// The number of hidden items.
+    pub fn hidden_count(&self) -> usize {
+        self.model.len() - self.rows.len()
+    }
+
+    /// Retrieve the index of an item in the source model from its row in this proxy.
+    pub fn source_index(&self, row: usize) -> Option<usize> {
+        self.rows
+            .source_index(row)
+            .filter(|index| *index < self.model.len())
+    }
+
+    /// Retrieve the row of an item from its index in the source model. Returns [None] if the item is hidden.
+    pub fn proxy_index(&self, source_index: usize) -> Option<usize> {
+        self.rows.row(source_index)
+    }
+}
+
+impl<M, I> ListModel<I> for HidingProxyModel<'_, M, I>
+where
+    M: ListModel<I>,
+{
+    fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    fn item(&self, index: usize) -> Option<&I> {
+        self.source_index(index)
+            .and_then(|source_index| self.model.item(source_index))
+    }
+
+    fn item_mut(&mut self, index: usize) -> Option<&mut I> {
+        self.source_index(index)
+            .and_then(|source_index| self.model.item_mut(source_index))
+    }
+
+    fn add(&mut self, item: I) {
+        self.model.add(item);
+        self.refresh();
+    }
+
+    /// Inserts the item in the source model before the item at the given row.
+    fn insert(&mut self, index: usize, item: I) {
+        let source_index = if index == self.len() {
+            Some(self.model.len())
+        } else {
+            self.source_index(index)
+        };
+        if let Some(source_index) = source_index {
+            self.model.insert(source_index, item);
+            self.refresh();
+        }
+    }
+
+    fn remove(&mut self, index: usize) {
+        if let Some(source_index) = self.source_index(index) {
+            self.model.remove(source_index);
+            self.refresh();
+        }
+    }
+
+    fn swap_items(&mut self, a: usize, b: usize) {
+        if let (Some(a), Some(b)) = (self.source_index(a), self.source_index(b)) {
+            self.model.swap_items(a, b);
+            self.refresh();
+        }
+    }
+
+    fn move_item(&mut self, from: usize, to: usize) {
+        if let (Some(from), Some(to)) = (self.source_index(from), self.source_index(to)) {
+            self.model.move_item(from, to);
+            self.refresh();
+        }
+    }
+
+    fn copy(&mut self, a: usize, b: usize) {
+        if let (Some(a), Some(b)) = (self.source_index(a), self.source_index(b)) {
+            self.model.copy(a, b);
+            self.refresh();
+        }
+    }
+
+    fn row_to_index(&self, row_number: usize) -> Option<usize> {
+        self.source_index(row_number)
+            .and_then(|index| self.model.row_to_index(index))
+    }
+
+    fn generation(&self) -> Option<u64> {
+        self.model.generation()
+    }
+}
@@ -0,0 +1,104 @@
+use std::hash::Hash;
+
+use egui::{Id, Response, ScrollArea, Sense, Spinner, Ui};
+
+use crate::{ListModel, PagedListModel};
+
+/// Shows the rows of a [PagedListModel] in a scroll area and requests the next page
+/// when the user scrolls near the end. A loading row is shown while a page is in flight.
+///
+/// Widgets with their own scroll area can page the model with [InfiniteScroll::load_near] instead,
+/// ex. an [crate::EditableList] or [crate::TableView] which reports its last visible row.
+pub struct InfiniteScroll<'a> {
+    id: Id,
+    threshold: usize,
+    loading_text: &'a str,
+}
+
+impl<'a> InfiniteScroll<'a> {
+    pub fn new(id_source: impl Hash) -> Self {
+        Self {
+            id: Id::new(id_source).with("infinite_scroll"),
+            threshold: 10,
+            loading_text: "Loading...",
+        }
+    }
+
+    /// Request the next page when the last visible row is within this many rows of the end. Defaults to 10.
+    pub fn threshold(mut self, threshold: usize) -> Self {
+        self.threshold = threshold;
+        self
+    }
+
+    pub fn loading_text(mut self, loading_text: &'a str) -> Self {
+        self.loading_text = loading_text;
+        self
+    }
+
+    /// Show every loaded row with `add_row`. Rows must have the same height.
+    pub fn show<I>(
+        self,
+        ui: &mut Ui,
+        model: &mut PagedListModel<I>,
+        row_height: f32,
+        mut add_row: impl FnMut(&mut Ui, usize, &mut I),
+    ) -> Response
+    where
+        I: Clone,
+    {
+        if model.update() {
+            ui.ctx().request_repaint();
+        }
+        let len = model.len();
+        let total_rows = if model.is_loading() { len + 1 } else { len };
+        let output = ScrollArea::vertical()
+            .id_source(self.id)
+            .auto_shrink([false, false])
+            .show_rows(ui, row_height, total_rows, |ui, range| {
+                for index in range.clone() {
+                    match model.item_mut(index) {
+                        Some(item) => add_row(ui, index, item),
+                        None => {
+                            ui.horizontal(|ui| {
+                                ui.add(Spinner::new());
+                                ui.weak(self.loading_text);
+                            });
+                        }
+                    }
+                }
+                range
+            });
+        self.request_pages(ui, model, output.inner.end);
+        ui.interact(output.inner_rect, self.id, Sense::hover())
+    }
+
+    /// Receive loaded pages and request the next one once the last visible row is near the end.
+    /// Call it every frame after showing the rows, ex. with the row reported by [crate::EditableList::last_visible_row]
+    /// or [crate::TableView::last_visible_row]. Without a visible row, pages load until the model has more rows than the threshold.
+    pub fn load_near<I>(
+        &self,
+        ui: &Ui,
+        model: &mut PagedListModel<I>,
+        last_visible_row: Option<usize>,
+    ) where
+        I: Clone,
+    {
+        if model.update() {
+            ui.ctx().request_repaint();
+        }
+        self.request_pages(ui, model, last_visible_row.map_or(0, |row| row + 1));
+    }
+
+    fn request_pages<I>(&self, ui: &Ui, model: &mut PagedListModel<I>, visible_end: usize)
+    where
+        I: Clone,
+    {
+        if visible_end + self.threshold >= model.len() {
+            model.request_next_page();
+        }
+        if model.is_loading() {
+            // Poll for the page until it arrives.
+            ui.ctx().request_repaint();
+        }
+    }
+}
@@ -0,0 +1,98 @@
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+
+use crate::ListModel;
+
+/// An in-process clipboard for model items. Items are cloned rather than serialized,
+/// so any [Clone] type can be cut, copied, and pasted between widgets during a session.
+/// Each item type has its own slot.
+#[derive(Default)]
+pub struct ItemClipboard {
+    slots: HashMap<TypeId, Box<dyn Any>>,
+}
+
+impl ItemClipboard {
+    /// Replace the clipboard contents for items of type `T`.
+    pub fn set<T>(&mut self, items: Vec<T>)
+    where
+        T: Clone + 'static,
+    {
+        self.slots.insert(TypeId::of::<T>(), Box::new(items));
+    }
+
+    /// Retrieve the clipboard contents for items of type `T`.
+    pub fn get<T>(&self) -> Option<&[T]>
+    where
+        T: Clone + 'static,
+    {
+        self.slots
+            .get(&TypeId::of::<T>())
+            .and_then(|items| items.downcast_ref::<Vec<T>>())
+            .map(|items| items.as_slice())
+    }
+
+    /// Whether the clipboard holds any items of type `T`.
+    pub fn has<T>(&self) -> bool
+    where
+        T: Clone + 'static,
+    {
+        self.get::<T>().is_some_and(|items| !items.is_empty())
+    }
+
+    /// Remove the clipboard contents for items of type `T`.
+    pub fn clear<T>(&mut self)
+    where
+        T: Clone + 'static,
+    {
+        self.slots.remove(&TypeId::of::<T>());
+    }
+
+    /// Copy the items at the given indices (in the given order) to the clipboard.
+    pub fn copy_from<M, I>(&mut self, model: &M, indices: impl IntoIterator<Item = usize>)
+    where
+        M: ListModel<I>,
+        I: Clone + 'static,
+    {
+        let items = indices
+            .into_iter()
+            .filter_map(|index| model.item(index).cloned())
+            .collect();
+        self.set::<I>(items);
+    }
+
+    /// Copy the items at the given indices to the clipboard, then remove them from the model.
+    pub fn cut_from<M, I>(&mut self, model: &mut M, indices: impl IntoIterator<Item = usize>)
+    where
+        M: ListModel<I>,
+        I: Clone + 'static,
+    {
+        let mut indices: Vec<usize> = indices.into_iter().collect();
+        self.copy_from(model, indices.iter().copied());
+        indices.sort_unstable();
+        indices.dedup();
+        for index in indices.into_iter().rev() {
+            model.remove(index);
+        }
+    }
+
+    /// Insert the clipboard contents into the model starting at the given index.
+    /// Items are added to the end if the index is out of bounds. Returns the number of items pasted.
+    pub fn paste_into<M, I>(&self, model: &mut M, index: usize) -> usize
+    where
+        M: ListModel<I>,
+        I: Clone + 'static,
+    {
+        let Some(items) = self.get::<I>() else {
+            return 0;
+        };
+        for (offset, item) in items.iter().enumerate() {
+            let index = index + offset;
+            if index <= model.len() {
+                model.insert(index, item.clone());
+            } else {
+                model.add(item.clone());
+            }
+        }
+        items.len()
+    }
+}
@@ -1,16 +1,91 @@
 use std::borrow::Cow;
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, VecDeque};
+use std::ops::Bound;
 
-use egui::Image;
+use egui::{Color32, Id, Image, RichText, Visuals};
 use indexmap::IndexMap;
 
+use crate::model_error::check_index;
+use crate::{KeyPolicy, ModelError, ModelSnapshot, RenameError, TextCollator};
+
 /// Where the decoration will be displayed. Used to provide context when requesting a decoration from an item.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum DecorationKind<'a> {
     List,
     DropDown,
+    Table,
     Other(&'a str),
 }
 
+/// A color for item text which follows the current theme.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ItemColor {
+    Error,
+    Warning,
+    Custom(Color32),
+}
+
+/// How an item's text should be rendered, ex. red for invalid items or struck through for deleted items.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct ItemStyle {
+    pub color: Option<ItemColor>,
+    pub strong: bool,
+    pub weak: bool,
+    pub italics: bool,
+    pub strikethrough: bool,
+}
+
+impl ItemStyle {
+    /// Red text for items which fail validation.
+    pub fn invalid() -> Self {
+        Self {
+            color: Some(ItemColor::Error),
+            ..Default::default()
+        }
+    }
+
+    /// Dim, struck through text for deleted or deprecated items.
+    pub fn deprecated() -> Self {
+        Self {
+            weak: true,
+            strikethrough: true,
+            ..Default::default()
+        }
+    }
+
+    /// Emphasized text for items with unsaved changes.
+    pub fn modified() -> Self {
+        Self {
+            strong: true,
+            ..Default::default()
+        }
+    }
+
+    /// Apply the style to some text.
+    pub fn apply(&self, text: impl Into<RichText>, visuals: &Visuals) -> RichText {
+        let mut text = text.into();
+        if self.strong {
+            text = text.strong();
+        }
+        if self.weak {
+            text = text.weak();
+        }
+        if self.italics {
+            text = text.italics();
+        }
+        if self.strikethrough {
+            text = text.strikethrough();
+        }
+        match self.color {
+            Some(ItemColor::Error) => text.color(visuals.error_fg_color),
+            Some(ItemColor::Warning) => text.color(visuals.warn_fg_color),
+            Some(ItemColor::Custom(color)) => text.color(color),
+            None => text,
+        }
+    }
+}
+
 /// An item that could be rendered in a view. Typically part of a collection of similar items stored in a model.
 pub trait ViewItem: Clone {
     type DecorationDependencies;
@@ -22,11 +97,77 @@ pub trait ViewItem: Clone {
         false
     }
 
+    /// Whether this item can be moved by the user.
+    /// Locked items (ex. headers or mandatory first entries) stay in place when the rest of a list is reordered.
+    fn reorderable(&self) -> bool {
+        true
+    }
+
     /// Retrieve the display text for this item using the given dependencies.
     fn with_text<F, R>(&self, consumer: F) -> R
     where
         F: FnOnce(&str) -> R;
 
+    /// How widgets should render this item's text.
+    fn style_hint(&self) -> ItemStyle {
+        ItemStyle::default()
+    }
+
+    /// The group this item belongs to. Widgets show grouped items under collapsible headers.
+    fn group_key(&self) -> Option<Cow<'_, str>> {
+        None
+    }
+
+    /// A short label shown after the item's text, ex. a usage count, "NEW", or a level number.
+    fn badge(&self) -> Option<Cow<'_, str>> {
+        None
+    }
+
+    /// Retrieve secondary text for this item, ex. its key or where it's used.
+    /// Widgets display it as a smaller, dimmer line below the main text.
+    fn with_subtitle<F, R>(&self, consumer: F) -> R
+    where
+        F: FnOnce(Option<&str>) -> R,
+    {
+        consumer(None)
+    }
+
+    /// Retrieve a short description of this item which widgets show on hover.
+    fn with_tooltip<F, R>(&self, consumer: F) -> R
+    where
+        F: FnOnce(Option<&str>) -> R,
+    {
+        consumer(None)
+    }
+
+    /// An ID which follows this item when it is moved or renamed. Widgets use it to salt the [Id]s of
+    /// row contents so collapsing state and text edit focus stay with the item.
+    /// Defaults to [None], in which case rows use their position.
+    /// Keyed widgets fall back to an ID built from the item's key instead (see [KeyedViewItem::keyed_stable_id]).
+    fn stable_id(&self) -> Option<Id> {
+        None
+    }
+
+    /// Whether the item matches a search query, ex. on its key or tags as well as its text.
+    /// Defaults to checking whether the text contains the query.
+    fn matches(&self, query: &str) -> bool {
+        self.with_text(|text| text.contains(query))
+    }
+
+    /// The key used to order this item when sorting. Defaults to the display text.
+    fn sort_key(&self) -> SortKey {
+        self.with_text(|text| SortKey::Text(text.to_string()))
+    }
+
+    /// Whether this item is checked. Returns [None] if the item can't be checked.
+    /// Widgets show a checkbox for checkable items.
+    fn is_checked(&self) -> Option<bool> {
+        None
+    }
+
+    /// Check or uncheck this item. Does nothing if the item can't be checked.
+    fn set_checked(&mut self, _checked: bool) {}
+
     /// Retrieve the decoration for this item and the recommended scale to display it with.
     /// The [DecorationKind] may be used to provide different decorations based on the context.
     #[allow(unused)]
@@ -43,12 +184,96 @@ pub trait ViewItem: Clone {
     }
 }
 
+/// A value used to order items, ex. a level or chapter number instead of the item's label.
+/// Keys of different kinds order numbers first, then text, then composite keys.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SortKey {
+    Number(i64),
+    Text(String),
+    /// Several keys compared in order, ex. chapter then name.
+    Composite(Vec<SortKey>),
+}
+
+impl SortKey {
+    /// Compare two keys, using the collator for text.
+    pub fn compare(&self, other: &SortKey, collator: &dyn TextCollator) -> Ordering {
+        match (self, other) {
+            (SortKey::Text(a), SortKey::Text(b)) => collator.compare(a, b),
+            (SortKey::Composite(a), SortKey::Composite(b)) => a
+                .iter()
+                .zip(b)
+                .map(|(a, b)| a.compare(b, collator))
+                .find(|ordering| ordering.is_ne())
+                .unwrap_or_else(|| a.len().cmp(&b.len())),
+            (a, b) => a.cmp(b),
+        }
+    }
+}
+
+impl From<i64> for SortKey {
+    fn from(value: i64) -> Self {
+        SortKey::Number(value)
+    }
+}
+
+impl From<String> for SortKey {
+    fn from(value: String) -> Self {
+        SortKey::Text(value)
+    }
+}
+
+impl From<&str> for SortKey {
+    fn from(value: &str) -> Self {
+        SortKey::Text(value.to_string())
+    }
+}
+
+/// Split rows into groups by [ViewItem::group_key]. Groups are ordered by their first row.
+/// Rows without a group key are collected in a group with no name.
+pub fn group_rows<M, I>(
+    model: &M,
+    rows: impl IntoIterator<Item = usize>,
+) -> Vec<(Option<String>, Vec<usize>)>
+where
+    M: ListModel<I>,
+    I: ViewItem,
+{
+    let mut groups: IndexMap<Option<String>, Vec<usize>> = IndexMap::new();
+    for row in rows {
+        let key = model
+            .item(row)
+            .and_then(|item| item.group_key().map(Cow::into_owned));
+        groups.entry(key).or_default().push(row);
+    }
+    groups.into_iter().collect()
+}
+
+/// Whether the user may move the item at index `from` to index `to`.
+/// Both the item being moved and the item currently at the destination must be [ViewItem::reorderable].
+pub fn can_reorder<M, I>(model: &M, from: usize, to: usize) -> bool
+where
+    M: ListModel<I>,
+    I: ViewItem,
+{
+    match (model.item(from), model.item(to)) {
+        (Some(a), Some(b)) => a.reorderable() && b.reorderable(),
+        _ => false,
+    }
+}
+
 /// A [ViewItem] that has a unique ID distinguishing it from other items.
 pub trait KeyedViewItem: ViewItem {
     /// Retrieve the key from this item.
     fn key(&self) -> Cow<'_, str>;
 
     fn set_key(&mut self, key: String);
+
+    /// The item's [ViewItem::stable_id], or an ID built from its key. Keyed drop downs and lists with
+    /// [crate::EditableList::keyed_item_hooks] use it, so rows follow their items when they're reordered.
+    fn keyed_stable_id(&self) -> Option<Id> {
+        self.stable_id()
+            .or_else(|| Some(Id::new(self.key().as_ref())))
+    }
 }
 
 /// An array-like of [ViewItem] that can be rendered in collection widgets.
@@ -80,8 +305,183 @@ pub trait ListModel<I> {
     /// Copy the contents of index `a` to index `b`.
     fn copy(&mut self, a: usize, b: usize);
 
+    /// Move the item at index `from` to index `to`, shifting the items in between (if in bounds).
+    /// The default implementation swaps adjacent items, which preserves the order of the other items.
+    fn move_item(&mut self, from: usize, to: usize) {
+        if from >= self.len() || to >= self.len() {
+            return;
+        }
+        if from < to {
+            for i in from..to {
+                self.swap_items(i, i + 1);
+            }
+        } else {
+            for i in (to..from).rev() {
+                self.swap_items(i, i + 1);
+            }
+        }
+    }
+
     /// Convert a row number to its index in the underlying collection.
+    /// Proxies resolve through their source, so layered proxies return the index in the original collection.
     fn row_to_index(&self, row_number: usize) -> Option<usize>;
+
+    /// Iterate over the items in this model. Indices without an item are skipped.
+    fn iter<'a>(&'a self) -> impl Iterator<Item = &'a I>
+    where
+        Self: Sized,
+        I: 'a,
+    {
+        (0..self.len()).filter_map(|index| self.item(index))
+    }
+
+    /// Iterate over the items in this model along with their indices.
+    fn iter_indexed<'a>(&'a self) -> impl Iterator<Item = (usize, &'a I)>
+    where
+        Self: Sized,
+        I: 'a,
+    {
+        (0..self.len()).filter_map(|index| self.item(index).map(|item| (index, item)))
+    }
+
+    /// Visit every item in this model mutably along with its index.
+    fn for_each_mut(&mut self, mut visitor: impl FnMut(usize, &mut I))
+    where
+        Self: Sized,
+    {
+        for index in 0..self.len() {
+            if let Some(item) = self.item_mut(index) {
+                visitor(index, item);
+            }
+        }
+    }
+
+    /// Remove the items at the given indices. Indices refer to the model before any removal,
+    /// and duplicate or out of bounds indices are ignored.
+    fn remove_many(&mut self, indices: &[usize]) {
+        let mut indices = indices.to_vec();
+        indices.sort_unstable();
+        indices.dedup();
+        for index in indices.into_iter().rev() {
+            self.remove(index);
+        }
+    }
+
+    /// Remove every item for which `keep` returns false.
+    fn retain(&mut self, mut keep: impl FnMut(&I) -> bool)
+    where
+        Self: Sized,
+    {
+        let indices: Vec<usize> = self
+            .iter_indexed()
+            .filter(|(_, item)| !keep(item))
+            .map(|(index, _)| index)
+            .collect();
+        self.remove_many(&indices);
+    }
+
+    /// A counter which changes whenever the model changes, so caches like proxy row maps can detect
+    /// stale data (see [crate::InvalidationHandle]). Returns [None] if the model doesn't track changes,
+    /// in which case callers must refresh caches themselves. See [crate::ObservableModel].
+    fn generation(&self) -> Option<u64> {
+        None
+    }
+
+    /// Remove every item.
+    fn clear(&mut self) {
+        let indices: Vec<usize> = (0..self.len()).collect();
+        self.remove_many(&indices);
+    }
+
+    /// Add every item to the end of this model, ex. when importing records in bulk.
+    /// Named so it doesn't clash with [Extend::extend] on collections which implement both.
+    fn extend_items(&mut self, items: impl IntoIterator<Item = I>)
+    where
+        Self: Sized,
+    {
+        for item in items {
+            self.add(item);
+        }
+    }
+
+    /// Clone every item so the model can be put back in this state with [ListModel::restore].
+    /// This is a deep copy which costs O(n) clones, so take it once (ex. when a dialog opens) rather than every frame.
+    fn clone_snapshot(&self) -> ModelSnapshot<I>
+    where
+        Self: Sized,
+        I: Clone,
+    {
+        ModelSnapshot::new(self.iter().cloned().collect())
+    }
+
+    /// Replace every item with the items from a snapshot.
+    fn restore(&mut self, snapshot: &ModelSnapshot<I>)
+    where
+        Self: Sized,
+        I: Clone,
+    {
+        self.clear();
+        self.extend_items(snapshot.items().iter().cloned());
+    }
+
+    /// Add an item to the end of this model, reporting why it failed.
+    fn try_add(&mut self, item: I) -> Result<(), ModelError> {
+        let len = self.len();
+        self.add(item);
+        if self.len() > len {
+            Ok(())
+        } else {
+            Err(ModelError::Rejected)
+        }
+    }
+
+    /// Insert an item at the specified index, reporting why it failed.
+    fn try_insert(&mut self, index: usize, item: I) -> Result<(), ModelError> {
+        let len = self.len();
+        check_index(index, len + 1)?;
+        self.insert(index, item);
+        if self.len() > len {
+            Ok(())
+        } else {
+            Err(ModelError::Rejected)
+        }
+    }
+
+    /// Remove the item at the given index, reporting why it failed.
+    fn try_remove(&mut self, index: usize) -> Result<(), ModelError> {
+        let len = self.len();
+        check_index(index, len)?;
+        self.remove(index);
+        if self.len() < len {
+            Ok(())
+        } else {
+            Err(ModelError::Rejected)
+        }
+    }
+
+    /// Swap items at the given indices, reporting why it failed.
+    fn try_swap_items(&mut self, a: usize, b: usize) -> Result<(), ModelError> {
+        check_index(a, self.len())?;
+        check_index(b, self.len())?;
+        self.swap_items(a, b);
+        Ok(())
+    }
+
+    /// Move the item at index `from` to index `to`, reporting why it failed.
+    fn try_move_item(&mut self, from: usize, to: usize) -> Result<(), ModelError> {
+        check_index(from, self.len())?;
+        check_index(to, self.len())?;
+        self.move_item(from, to);
+        Ok(())
+    }
+
+    /// Copy the contents of index `a` to index `b`, reporting why it failed.
+    fn try_copy(&mut self, a: usize, b: usize) -> Result<(), ModelError> {
+        check_index(a, self.len())?;
+        check_index(b, self.len())?;
+        self.copy(a, b);
+        Ok(())
+    }
 }
 
 impl<I> ListModel<I> for Vec<I>
@@ -108,6 +508,14 @@ where
         self.push(item);
     }
 
+    fn clear(&mut self) {
+        Vec::clear(self);
+    }
+
+    fn extend_items(&mut self, items: impl IntoIterator<Item = I>) {
+        Extend::extend(self, items);
+    }
+
     fn insert(&mut self, index: usize, item: I) {
         if index <= self.len() {
             self.insert(index, item);
@@ -132,6 +540,29 @@ where
         }
     }
 
+    fn move_item(&mut self, from: usize, to: usize) {
+        if from < to && to < self.len() {
+            self[from..=to].rotate_left(1);
+        } else if to < from && from < self.len() {
+            self[to..=from].rotate_right(1);
+        }
+    }
+
+    fn remove_many(&mut self, indices: &[usize]) {
+        let mut indices = indices.to_vec();
+        indices.sort_unstable();
+        let mut index = 0;
+        Vec::retain(self, |_| {
+            let keep = indices.binary_search(&index).is_err();
+            index += 1;
+            keep
+        });
+    }
+
+    fn retain(&mut self, keep: impl FnMut(&I) -> bool) {
+        Vec::retain(self, keep);
+    }
+
     fn row_to_index(&self, row_number: usize) -> Option<usize> {
         (0..self.len()).contains(&row_number).then_some(row_number)
     }
@@ -164,6 +595,18 @@ where
         }
     }
 
+    fn clear(&mut self) {
+        IndexMap::clear(self);
+    }
+
+    fn extend_items(&mut self, items: impl IntoIterator<Item = I>) {
+        let items = items.into_iter();
+        self.reserve(items.size_hint().0);
+        for item in items {
+            ListModel::add(self, item);
+        }
+    }
+
     fn insert(&mut self, index: usize, item: I) {
         if index <= self.len() {
             self.add(item);
@@ -192,9 +635,272 @@ where
         }
     }
 
+    fn move_item(&mut self, from: usize, to: usize) {
+        if from < self.len() && to < self.len() {
+            self.move_index(from, to);
+        }
+    }
+
     fn row_to_index(&self, row_number: usize) -> Option<usize> {
         (0..self.len()).contains(&row_number).then_some(row_number)
     }
+
+    fn try_add(&mut self, item: I) -> Result<(), ModelError> {
+        check_unique_key(&item, |key| self.contains_key(key))?;
+        ListModel::add(self, item);
+        Ok(())
+    }
+
+    fn try_insert(&mut self, index: usize, item: I) -> Result<(), ModelError> {
+        check_index(index, self.len() + 1)?;
+        check_unique_key(&item, |key| self.contains_key(key))?;
+        ListModel::insert(self, index, item);
+        Ok(())
+    }
+}
+
+impl<I> ListModel<I> for VecDeque<I>
+where
+    I: ViewItem,
+{
+    fn is_empty(&self) -> bool {
+        self.is_empty()
+    }
+
+    fn len(&self) -> usize {
+        self.len()
+    }
+
+    fn item(&self, index: usize) -> Option<&I> {
+        self.get(index)
+    }
+
+    fn item_mut(&mut self, index: usize) -> Option<&mut I> {
+        self.get_mut(index)
+    }
+
+    fn add(&mut self, item: I) {
+        self.push_back(item);
+    }
+
+    fn insert(&mut self, index: usize, item: I) {
+        if index <= self.len() {
+            self.insert(index, item);
+        }
+    }
+
+    fn remove(&mut self, index: usize) {
+        self.remove(index);
+    }
+
+    fn swap_items(&mut self, a: usize, b: usize) {
+        if a < self.len() && b < self.len() {
+            self.swap(a, b);
+        }
+    }
+
+    fn copy(&mut self, a: usize, b: usize) {
+        if a < self.len() && b < self.len() {
+            self[b] = self[a].clone();
+        }
+    }
+
+    fn move_item(&mut self, from: usize, to: usize) {
+        if from < self.len() && to < self.len() {
+            if let Some(item) = self.remove(from) {
+                self.insert(to, item);
+            }
+        }
+    }
+
+    fn row_to_index(&self, row_number: usize) -> Option<usize> {
+        (0..self.len()).contains(&row_number).then_some(row_number)
+    }
+}
+
+/// Items are always ordered by key, so [ListModel::insert] ignores the index
+/// and [ListModel::swap_items] does nothing.
+impl<I> ListModel<I> for BTreeMap<String, I>
+where
+    I: KeyedViewItem,
+{
+    fn is_empty(&self) -> bool {
+        self.is_empty()
+    }
+
+    fn len(&self) -> usize {
+        self.len()
+    }
+
+    fn item(&self, index: usize) -> Option<&I> {
+        self.values().nth(index)
+    }
+
+    fn item_mut(&mut self, index: usize) -> Option<&mut I> {
+        self.values_mut().nth(index)
+    }
+
+    fn add(&mut self, item: I) {
+        let key = item.key();
+        if !self.contains_key(key.as_ref()) {
+            self.insert(key.into_owned(), item);
+        }
+    }
+
+    fn insert(&mut self, index: usize, item: I) {
+        if index <= self.len() {
+            ListModel::add(self, item);
+        }
+    }
+
+    fn remove(&mut self, index: usize) {
+        if let Some(key) = self.keys().nth(index).cloned() {
+            self.remove(&key);
+        }
+    }
+
+    fn move_item(&mut self, _: usize, _: usize) {}
+
+    fn swap_items(&mut self, _: usize, _: usize) {}
+
+    fn try_add(&mut self, item: I) -> Result<(), ModelError> {
+        check_unique_key(&item, |key| self.contains_key(key))?;
+        ListModel::add(self, item);
+        Ok(())
+    }
+
+    fn try_insert(&mut self, index: usize, item: I) -> Result<(), ModelError> {
+        check_index(index, self.len() + 1)?;
+        check_unique_key(&item, |key| self.contains_key(key))?;
+        ListModel::add(self, item);
+        Ok(())
+    }
+
+    fn try_swap_items(&mut self, _: usize, _: usize) -> Result<(), ModelError> {
+        Err(ModelError::Unsupported("reordering a BTreeMap"))
+    }
+
+    fn try_move_item(&mut self, _: usize, _: usize) -> Result<(), ModelError> {
+        Err(ModelError::Unsupported("reordering a BTreeMap"))
+    }
+
+    fn copy(&mut self, a: usize, b: usize) {
+        if let Some(key) = self.keys().nth(b).cloned() {
+            if let Some(mut a) = self.values().nth(a).cloned() {
+                a.set_key(key.clone());
+                self.insert(key, a);
+            }
+        }
+    }
+
+    fn row_to_index(&self, row_number: usize) -> Option<usize> {
+        (0..self.len()).contains(&row_number).then_some(row_number)
+    }
+}
+
+fn check_unique_key<I>(item: &I, exists: impl Fn(&str) -> bool) -> Result<(), ModelError>
+where
+    I: KeyedViewItem,
+{
+    let key = item.key();
+    if exists(key.as_ref()) {
+        Err(ModelError::DuplicateKey(key.into_owned()))
+    } else {
+        Ok(())
+    }
+}
+
+/// A hierarchy of [ViewItem]. Nodes are addressed by their path from the root, where each entry is a child index.
+/// The empty path refers to the root, which holds no item of its own.
+pub trait TreeModel<I> {
+    /// The number of children under the node at the given path.
+    fn child_count(&self, path: &[usize]) -> usize;
+
+    /// Retrieve the item at the given path (if it exists)
+    fn item(&self, path: &[usize]) -> Option<&I>;
+
+    /// Retrieve a mutable reference to the item at the given path (if it exists)
+    fn item_mut(&mut self, path: &[usize]) -> Option<&mut I>;
+
+    /// The path of the parent of the given node. Top level nodes have the root (an empty path) as their parent.
+    fn parent<'p>(&self, path: &'p [usize]) -> Option<&'p [usize]> {
+        path.split_last().map(|(_, parent)| parent)
+    }
+
+    /// The paths of the children of the given node.
+    fn children(&self, path: &[usize]) -> Vec<Vec<usize>> {
+        (0..self.child_count(path))
+            .map(|i| {
+                let mut child = path.to_vec();
+                child.push(i);
+                child
+            })
+            .collect()
+    }
+}
+
+/// A node in a simple owned tree. A [Vec] of these implements [TreeModel].
+#[derive(Debug, Clone, Default)]
+pub struct TreeNode<I> {
+    pub item: I,
+    pub children: Vec<TreeNode<I>>,
+}
+
+impl<I> TreeNode<I> {
+    pub fn new(item: I) -> Self {
+        Self {
+            item,
+            children: vec![],
+        }
+    }
+
+    pub fn with_children(item: I, children: Vec<TreeNode<I>>) -> Self {
+        Self { item, children }
+    }
+}
+
+fn tree_node<'a, I>(nodes: &'a [TreeNode<I>], path: &[usize]) -> Option<&'a TreeNode<I>> {
+    let (first, rest) = path.split_first()?;
+    let mut node = nodes.get(*first)?;
+    for index in rest {
+        node = node.children.get(*index)?;
+    }
+    Some(node)
+}
+
+fn tree_node_mut<'a, I>(
+    nodes: &'a mut [TreeNode<I>],
+    path: &[usize],
+) -> Option<&'a mut TreeNode<I>> {
+    let (first, rest) = path.split_first()?;
+    let mut node = nodes.get_mut(*first)?;
+    for index in rest {
+        node = node.children.get_mut(*index)?;
+    }
+    Some(node)
+}
+
+impl<I> TreeModel<I> for Vec<TreeNode<I>>
+where
+    I: ViewItem,
+{
+    fn child_count(&self, path: &[usize]) -> usize {
+        if path.is_empty() {
+            self.len()
+        } else {
+            tree_node(self, path)
+                .map(|node| node.children.len())
+                .unwrap_or_default()
+        }
+    }
+
+    fn item(&self, path: &[usize]) -> Option<&I> {
+        tree_node(self, path).map(|node| &node.item)
+    }
+
+    fn item_mut(&mut self, path: &[usize]) -> Option<&mut I> {
+        tree_node_mut(self, path).map(|node| &mut node.item)
+    }
 }
 
 /// A [ListModel] of items which have a unique ID.
@@ -209,6 +915,82 @@ pub trait KeyedListModel<I>: ListModel<I> {
     fn contains(&self, key: &str) -> bool {
         self.index_of(key).is_some()
     }
+
+    /// Add an item, first giving it a key from [KeyPolicy::make_unique] if its key is already taken.
+    /// Returns the key the item was added with.
+    fn add_with_unique_key(&mut self, mut item: I, policy: &KeyPolicy) -> String
+    where
+        Self: Sized,
+        I: KeyedViewItem,
+    {
+        let key = policy.make_unique(&item.key(), |key| self.contains(key));
+        item.set_key(key.clone());
+        self.add(item);
+        key
+    }
+
+    /// Change an item's key, keeping its position in models which don't order items by key.
+    /// Fails if no item has the old key or another item already has the new key.
+    fn rename_key(&mut self, old: &str, new: &str) -> Result<(), RenameError>
+    where
+        Self: Sized,
+        I: KeyedViewItem + Clone,
+    {
+        let index = self
+            .index_of(old)
+            .ok_or_else(|| RenameError::NotFound(old.to_string()))?;
+        if old == new {
+            return Ok(());
+        }
+        if self.contains(new) {
+            return Err(RenameError::Collision(new.to_string()));
+        }
+        let original = self.item(index).cloned().ok_or(RenameError::Rejected)?;
+        let mut renamed = original.clone();
+        renamed.set_key(new.to_string());
+        self.remove(index);
+        self.insert(index, renamed);
+        if self.contains(new) {
+            Ok(())
+        } else {
+            // Put the original back so a rejected rename doesn't lose the item.
+            self.insert(index, original);
+            Err(RenameError::Rejected)
+        }
+    }
+
+    /// Like [KeyedListModel::rename_key], but the new key is sanitized with the policy first.
+    /// Returns the key the item was renamed to.
+    fn rename_key_with_policy(
+        &mut self,
+        old: &str,
+        new: &str,
+        policy: &KeyPolicy,
+    ) -> Result<String, RenameError>
+    where
+        Self: Sized,
+        I: KeyedViewItem + Clone,
+    {
+        let new = policy.sanitize(new);
+        if new.is_empty() {
+            return Err(RenameError::Empty);
+        }
+        self.rename_key(old, &new).map(|_| new)
+    }
+
+    /// Insert a copy of the item at the index directly after it, with a unique key.
+    /// Returns the copy's key, or [None] if the index is out of bounds.
+    fn duplicate_with_unique_key(&mut self, index: usize, policy: &KeyPolicy) -> Option<String>
+    where
+        Self: Sized,
+        I: KeyedViewItem + Clone,
+    {
+        let mut item = self.item(index)?.clone();
+        let key = policy.make_unique(&item.key(), |key| self.contains(key));
+        item.set_key(key.clone());
+        self.insert(index + 1, item);
+        Some(key)
+    }
 }
 
 impl<I> KeyedListModel<I> for IndexMap<String, I>
@@ -219,3 +1001,138 @@ where
         self.get_index_of(key)
     }
 }
+
+impl<I> KeyedListModel<I> for BTreeMap<String, I>
+where
+    I: KeyedViewItem,
+{
+    fn index_of(&self, key: &str) -> Option<usize> {
+        self.contains_key(key).then(|| {
+            self.range::<str, _>((Bound::Unbounded, Bound::Excluded(key)))
+                .count()
+        })
+    }
+}
+
+/// Arena entries are ordered by their key, so [ListModel::insert] ignores the index.
+#[cfg(feature = "slab")]
+impl<I> ListModel<I> for slab::Slab<I>
+where
+    I: ViewItem,
+{
+    fn is_empty(&self) -> bool {
+        self.is_empty()
+    }
+
+    fn len(&self) -> usize {
+        self.len()
+    }
+
+    fn item(&self, index: usize) -> Option<&I> {
+        slab::Slab::iter(self).nth(index).map(|(_, v)| v)
+    }
+
+    fn item_mut(&mut self, index: usize) -> Option<&mut I> {
+        self.iter_mut().nth(index).map(|(_, v)| v)
+    }
+
+    fn add(&mut self, item: I) {
+        self.insert(item);
+    }
+
+    fn insert(&mut self, index: usize, item: I) {
+        if index <= self.len() {
+            self.insert(item);
+        }
+    }
+
+    fn remove(&mut self, index: usize) {
+        if let Some(key) = slab::Slab::iter(self).nth(index).map(|(k, _)| k) {
+            self.remove(key);
+        }
+    }
+
+    fn swap_items(&mut self, a: usize, b: usize) {
+        let key_a = slab::Slab::iter(self).nth(a).map(|(k, _)| k);
+        let key_b = slab::Slab::iter(self).nth(b).map(|(k, _)| k);
+        if let (Some(key_a), Some(key_b)) = (key_a, key_b) {
+            if let Some((a, b)) = self.get2_mut(key_a, key_b) {
+                std::mem::swap(a, b);
+            }
+        }
+    }
+
+    fn copy(&mut self, a: usize, b: usize) {
+        if let Some(a) = ListModel::item(self, a).cloned() {
+            if let Some(b) = ListModel::item_mut(self, b) {
+                *b = a;
+            }
+        }
+    }
+
+    fn row_to_index(&self, row_number: usize) -> Option<usize> {
+        (0..self.len()).contains(&row_number).then_some(row_number)
+    }
+}
+
+/// Arena entries are ordered by their key, so [ListModel::insert] ignores the index.
+#[cfg(feature = "slotmap")]
+impl<K, I> ListModel<I> for slotmap::SlotMap<K, I>
+where
+    K: slotmap::Key,
+    I: ViewItem,
+{
+    fn is_empty(&self) -> bool {
+        self.is_empty()
+    }
+
+    fn len(&self) -> usize {
+        self.len()
+    }
+
+    fn item(&self, index: usize) -> Option<&I> {
+        self.values().nth(index)
+    }
+
+    fn item_mut(&mut self, index: usize) -> Option<&mut I> {
+        self.values_mut().nth(index)
+    }
+
+    fn add(&mut self, item: I) {
+        self.insert(item);
+    }
+
+    fn insert(&mut self, index: usize, item: I) {
+        if index <= self.len() {
+            self.insert(item);
+        }
+    }
+
+    fn remove(&mut self, index: usize) {
+        if let Some(key) = self.keys().nth(index) {
+            self.remove(key);
+        }
+    }
+
+    fn swap_items(&mut self, a: usize, b: usize) {
+        let key_a = self.keys().nth(a);
+        let key_b = self.keys().nth(b);
+        if let (Some(key_a), Some(key_b)) = (key_a, key_b) {
+            if let Some([a, b]) = self.get_disjoint_mut([key_a, key_b]) {
+                std::mem::swap(a, b);
+            }
+        }
+    }
+
+    fn copy(&mut self, a: usize, b: usize) {
+        if let Some(a) = ListModel::item(self, a).cloned() {
+            if let Some(b) = ListModel::item_mut(self, b) {
+                *b = a;
+            }
+        }
+    }
+
+    fn row_to_index(&self, row_number: usize) -> Option<usize> {
+        (0..self.len()).contains(&row_number).then_some(row_number)
+    }
+}
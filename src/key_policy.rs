@@ -0,0 +1,136 @@
+/// Case normalization applied to keys.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum KeyCase {
+    #[default]
+    Preserve,
+    Upper,
+    Lower,
+}
+
+/// How a key is made unique when it collides with an existing key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SuffixStrategy {
+    /// Append a counter after a separator, ex. "KEY", "KEY_2", "KEY_3".
+    Counter { separator: String, start: usize },
+    /// Append a zero padded counter after a separator, ex. "KEY_001", "KEY_002".
+    Padded {
+        separator: String,
+        start: usize,
+        width: usize,
+    },
+}
+
+impl Default for SuffixStrategy {
+    fn default() -> Self {
+        Self::Counter {
+            separator: "_".to_string(),
+            start: 2,
+        }
+    }
+}
+
+impl SuffixStrategy {
+    fn start(&self) -> usize {
+        match self {
+            SuffixStrategy::Counter { start, .. } => *start,
+            SuffixStrategy::Padded { start, .. } => *start,
+        }
+    }
+
+    fn suffix(&self, counter: usize) -> String {
+        match self {
+            SuffixStrategy::Counter { separator, .. } => format!("{}{}", separator, counter),
+            SuffixStrategy::Padded {
+                separator, width, ..
+            } => format!("{}{:0width$}", separator, counter, width = *width),
+        }
+    }
+}
+
+/// Format rules for keys in a keyed model.
+/// Every path that produces keys (renames, generated keys, new entries) should run them through the same policy.
+#[derive(Debug, Clone)]
+pub struct KeyPolicy {
+    /// Characters allowed in a key.
+    pub allowed: fn(char) -> bool,
+    /// Replaces characters which are not allowed. If [None], they are removed instead.
+    pub replacement: Option<char>,
+    /// The maximum number of characters in a key.
+    pub max_length: Option<usize>,
+    pub case: KeyCase,
+    pub suffix: SuffixStrategy,
+}
+
+impl Default for KeyPolicy {
+    fn default() -> Self {
+        Self {
+            allowed: |c| !c.is_control(),
+            replacement: None,
+            max_length: None,
+            case: KeyCase::Preserve,
+            suffix: SuffixStrategy::default(),
+        }
+    }
+}
+
+impl KeyPolicy {
+    /// Upper case ASCII letters, digits, and underscores. Other characters are replaced with underscores.
+    pub fn identifier() -> Self {
+        Self {
+            allowed: |c| c.is_ascii_alphanumeric() || c == '_',
+            replacement: Some('_'),
+            case: KeyCase::Upper,
+            ..Default::default()
+        }
+    }
+
+    /// Apply the case, character, and length rules to a key.
+    pub fn sanitize(&self, key: &str) -> String {
+        let key = match self.case {
+            KeyCase::Preserve => key.to_string(),
+            KeyCase::Upper => key.to_uppercase(),
+            KeyCase::Lower => key.to_lowercase(),
+        };
+        let chars = key.chars().filter_map(|c| {
+            if (self.allowed)(c) {
+                Some(c)
+            } else {
+                self.replacement
+            }
+        });
+        match self.max_length {
+            Some(max_length) => chars.take(max_length).collect(),
+            None => chars.collect(),
+        }
+    }
+
+    /// Whether the key already follows this policy.
+    pub fn is_valid(&self, key: &str) -> bool {
+        self.sanitize(key) == key
+    }
+
+    /// Sanitize the key and add a suffix if it collides with an existing key.
+    pub fn make_unique(&self, key: &str, exists: impl Fn(&str) -> bool) -> String {
+        let base = self.sanitize(key);
+        if !exists(&base) {
+            return base;
+        }
+        let mut counter = self.suffix.start();
+        loop {
+            let suffix = self.suffix.suffix(counter);
+            let candidate = match self.max_length {
+                Some(max_length) => {
+                    let base_length = max_length.saturating_sub(suffix.chars().count());
+                    let mut candidate: String = base.chars().take(base_length).collect();
+                    candidate.push_str(&suffix);
+                    candidate
+                }
+                None => format!("{}{}", base, suffix),
+            };
+            if !exists(&candidate) {
+                return candidate;
+            }
+            counter += 1;
+        }
+    }
+}
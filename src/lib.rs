@@ -1,16 +1,86 @@
+pub mod alias_model;
+pub mod collation;
+pub mod command_queue;
+pub mod composite_list_model;
+pub mod debounced;
+pub mod decoration_cache;
 pub mod default_widgets;
+pub mod dirty_tracking_model;
 pub mod drop_down;
 pub mod editable_list;
 pub mod enum_combo_box;
+pub mod events;
 pub mod filter_proxy_item_model;
+pub mod flattened_tree_model;
+pub mod hiding_proxy_model;
+pub mod infinite_scroll;
+pub mod item_clipboard;
 pub mod item_model;
+pub mod key_policy;
+pub mod mapped_list_model;
+pub mod matching;
 pub mod misc_widgets;
+pub mod model_diff;
+pub mod model_error;
+#[cfg(feature = "serde")]
+pub mod model_persistence;
+pub mod model_snapshot;
+pub mod model_stats;
+pub mod observable_model;
+pub mod paged_list_model;
 pub mod property_grid;
+pub mod row_layout;
+pub mod row_map;
+pub mod search_text_cache;
+pub mod searchable_popup;
+pub mod selection_model;
+pub mod shared_model;
+pub mod sort_proxy_item_model;
+pub mod table_model;
+pub mod table_view;
+pub mod undoable_model;
+pub mod validation;
+pub mod virtual_list_model;
 
+pub use alias_model::*;
+pub use collation::*;
+pub use command_queue::*;
+pub use composite_list_model::*;
+pub use debounced::*;
+pub use decoration_cache::*;
 pub use default_widgets::*;
+pub use dirty_tracking_model::*;
 pub use drop_down::*;
 pub use editable_list::*;
+pub use events::*;
 pub use filter_proxy_item_model::*;
+pub use flattened_tree_model::*;
+pub use hiding_proxy_model::*;
+pub use infinite_scroll::*;
+pub use item_clipboard::*;
 pub use item_model::*;
+pub use key_policy::*;
+pub use mapped_list_model::*;
+pub use matching::*;
 pub use misc_widgets::*;
+pub use model_diff::*;
+pub use model_error::*;
+#[cfg(feature = "serde")]
+pub use model_persistence::*;
+pub use model_snapshot::*;
+pub use model_stats::*;
+pub use observable_model::*;
+pub use paged_list_model::*;
 pub use property_grid::*;
+pub use row_layout::*;
+pub use row_map::*;
+pub use search_text_cache::*;
+pub use searchable_popup::*;
+pub use selection_model::*;
+pub use shared_model::*;
+pub use sort_proxy_item_model::*;
+pub use table_model::*;
+pub use table_view::*;
+pub use undoable_model::*;
+pub use validation::*;
+pub use virtual_list_model::*;
@@ -0,0 +1,219 @@
+use std::cell::OnceCell;
+use std::marker::PhantomData;
+
+use crate::{ListModel, ModelError};
+
+/// Presents the items of a source model through a mapping function, ex. wrapping raw records
+/// in display friendly items with formatted labels. Mapped items are created on first access and cached.
+///
+/// The model is read only by default. Use [MappedListModel::with_passthrough] to forward mutations to the source.
+/// [ListModel::item_mut] always returns [None] since changes to a mapped item can't be written back.
+/// Use [MappedListModel::edit_source] instead.
+pub struct MappedListModel<'a, M, I, J> {
+    source: M,
+    map: Box<dyn Fn(&I) -> J + 'a>,
+    unmap: Option<Box<dyn Fn(J) -> I + 'a>>,
+    cache: Vec<OnceCell<J>>,
+    _item: PhantomData<I>,
+}
+
+impl<'a, M, I, J> MappedListModel<'a, M, I, J>
+where
+    M: ListModel<I>,
+{
+    pub fn new(source: M, map: impl Fn(&I) -> J + 'a) -> Self {
+        let mut model = Self {
+            source,
+            map: Box::new(map),
+            unmap: None,
+            cache: vec![],
+            _item: PhantomData,
+        };
+        model.invalidate();
+        model
+    }
+
+    /// Forward mutations to the source. `unmap` converts items added through this model back to source items.
+    pub fn with_passthrough(mut self, unmap: impl Fn(J) -> I + 'a) -> Self {
+        self.unmap = Some(Box::new(unmap));
+        self
+    }
+
+    pub fn is_read_only(&self) -> bool {
+        self.unmap.is_none()
+    }
+
+    pub fn source(&self) -> &M {
+        &self.source
+    }
+
+    /// Mutably access the source. Every mapped item is rebuilt afterwards.
+    pub fn source_mut(&mut self) -> &mut M {
+        self.invalidate();
+        &mut self.source
+    }
+
+    pub fn into_source(self) -> M {
+        self.source
+    }
+
+    /// Modify a source item and refresh its mapped item. Works even if the model is read only.
+    pub fn edit_source(&mut self, index: usize, edit: impl FnOnce(&mut I)) -> bool {
+        let Some(item) = self.source.item_mut(index) else {
+            return false;
+        };
+        edit(item);
+        if let Some(cell) = self.cache.get_mut(index) {
+            cell.take();
+        }
+        true
+    }
+
+    /// Discard every mapped item, ex. after the source changed or the mapping depends on outside state.
+    pub fn invalidate(&mut self) {
+        self.cache.clear();
+        self.cache.resize_with(self.source.len(), OnceCell::new);
+    }
+}
+
+impl<M, I, J> ListModel<J> for MappedListModel<'_, M, I, J>
+where
+    M: ListModel<I>,
+{
+    fn is_empty(&self) -> bool {
+        self.source.is_empty()
+    }
+
+    fn len(&self) -> usize {
+        self.source.len()
+    }
+
+    fn item(&self, index: usize) -> Option<&J> {
+        let cell = self.cache.get(index)?;
+        let item = self.source.item(index)?;
+        Some(cell.get_or_init(|| (self.map)(item)))
+    }
+
+    fn item_mut(&mut self, _: usize) -> Option<&mut J> {
+        None
+    }
+
+    fn add(&mut self, item: J) {
+        if let Some(unmap) = &self.unmap {
+            self.source.add(unmap(item));
+            self.cache.resize_with(self.source.len(), OnceCell::new);
+        }
+    }
+
+    fn insert(&mut self, index: usize, item: J) {
+        if let Some(unmap) = &self.unmap {
+            let len = self.source.len();
+            self.source.insert(index, unmap(item));
+            if self.source.len() > len && index <= self.cache.len() {
+                self.cache.insert(index, OnceCell::new());
+            }
+        }
+    }
+
+    fn remove(&mut self, index: usize) {
+        if self.unmap.is_some() && index < self.source.len() {
+            self.source.remove(index);
+            if index < self.cache.len() {
+                self.cache.remove(index);
+            }
+        }
+    }
+
+    fn swap_items(&mut self, a: usize, b: usize) {
+        if self.unmap.is_some() && a < self.cache.len() && b < self.cache.len() {
+            self.source.swap_items(a, b);
+            self.cache.swap(a, b);
+        }
+    }
+
+    fn move_item(&mut self, from: usize, to: usize) {
+        if self.unmap.is_some() && from < self.cache.len() && to < self.cache.len() {
+            self.source.move_item(from, to);
+            let cell = self.cache.remove(from);
+            self.cache.insert(to, cell);
+        }
+    }
+
+    fn copy(&mut self, a: usize, b: usize) {
+        if self.unmap.is_some() {
+            self.source.copy(a, b);
+            if let Some(cell) = self.cache.get_mut(b) {
+                cell.take();
+            }
+        }
+    }
+
+    fn row_to_index(&self, row_number: usize) -> Option<usize> {
+        self.source.row_to_index(row_number)
+    }
+
+    fn generation(&self) -> Option<u64> {
+        self.source.generation()
+    }
+
+    fn try_add(&mut self, item: J) -> Result<(), ModelError> {
+        match &self.unmap {
+            Some(unmap) => {
+                self.source.try_add(unmap(item))?;
+                self.cache.resize_with(self.source.len(), OnceCell::new);
+                Ok(())
+            }
+            None => Err(READ_ONLY),
+        }
+    }
+
+    fn try_insert(&mut self, index: usize, item: J) -> Result<(), ModelError> {
+        match &self.unmap {
+            Some(unmap) => {
+                self.source.try_insert(index, unmap(item))?;
+                self.cache.insert(index, OnceCell::new());
+                Ok(())
+            }
+            None => Err(READ_ONLY),
+        }
+    }
+
+    fn try_remove(&mut self, index: usize) -> Result<(), ModelError> {
+        if self.is_read_only() {
+            return Err(READ_ONLY);
+        }
+        self.source.try_remove(index)?;
+        self.cache.remove(index);
+        Ok(())
+    }
+
+    fn try_swap_items(&mut self, a: usize, b: usize) -> Result<(), ModelError> {
+        if self.is_read_only() {
+            return Err(READ_ONLY);
+        }
+        self.source.try_swap_items(a, b)?;
+        self.cache.swap(a, b);
+        Ok(())
+    }
+
+    fn try_move_item(&mut self, from: usize, to: usize) -> Result<(), ModelError> {
+        if self.is_read_only() {
+            return Err(READ_ONLY);
+        }
+        self.source.try_move_item(from, to)?;
+        let cell = self.cache.remove(from);
+        self.cache.insert(to, cell);
+        Ok(())
+    }
+
+    fn try_copy(&mut self, a: usize, b: usize) -> Result<(), ModelError> {
+        if self.is_read_only() {
+            return Err(READ_ONLY);
+        }
+        self.source.try_copy(a, b)?;
+        self.cache[b].take();
+        Ok(())
+    }
+}
+
+const READ_ONLY: ModelError = ModelError::Unsupported("modifying a read only MappedListModel");
@@ -0,0 +1,148 @@
+use std::ops::Range;
+
+/// How search text is matched against item text by filter proxies and drop downs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MatchMode {
+    /// The text contains the query.
+    #[default]
+    Contains,
+    /// The query's characters appear in the text in order, ex. "ssw" matches "Silver Sword".
+    /// Results are ranked so the closest matches come first.
+    Fuzzy,
+}
+
+impl MatchMode {
+    /// Score how well the text matches the query. Returns [None] if it doesn't match.
+    /// Higher scores are better matches. Case is compared as given.
+    pub fn score(&self, text: &str, query: &str) -> Option<u32> {
+        match self {
+            MatchMode::Contains => {
+                if text == query {
+                    Some(2)
+                } else if text.starts_with(query) {
+                    Some(1)
+                } else {
+                    text.contains(query).then_some(0)
+                }
+            }
+            MatchMode::Fuzzy => fuzzy_score(text, query),
+        }
+    }
+
+    /// The byte ranges of the text which matched the query, ex. to highlight them.
+    /// Adjacent matched characters are merged into one range.
+    pub fn match_ranges(
+        &self,
+        text: &str,
+        query: &str,
+        case_sensitive: bool,
+        whole_word: bool,
+    ) -> Vec<Range<usize>> {
+        if query.is_empty() {
+            return vec![];
+        }
+        let chars: Vec<(usize, char)> = text.char_indices().collect();
+        let query: Vec<char> = query.chars().collect();
+        let mut ranges: Vec<Range<usize>> = vec![];
+        match self {
+            MatchMode::Contains => {
+                let mut i = 0;
+                while i + query.len() <= chars.len() {
+                    let matched = query
+                        .iter()
+                        .zip(&chars[i..])
+                        .all(|(q, (_, c))| chars_eq(*c, *q, case_sensitive));
+                    if matched {
+                        let start = chars[i].0;
+                        let end = chars
+                            .get(i + query.len())
+                            .map_or(text.len(), |(index, _)| *index);
+                        if !whole_word || is_word(text, start, end) {
+                            ranges.push(start..end);
+                            i += query.len();
+                            continue;
+                        }
+                    }
+                    i += 1;
+                }
+            }
+            MatchMode::Fuzzy => {
+                let mut query = query.iter().peekable();
+                for (index, c) in chars {
+                    let Some(next) = query.peek() else {
+                        break;
+                    };
+                    if chars_eq(c, **next, case_sensitive) {
+                        query.next();
+                        let end = index + c.len_utf8();
+                        match ranges.last_mut() {
+                            Some(last) if last.end == index => last.end = end,
+                            _ => ranges.push(index..end),
+                        }
+                    }
+                }
+            }
+        }
+        ranges
+    }
+
+    /// Whether matches should be ordered by score instead of their original order.
+    pub fn ranks_results(&self) -> bool {
+        matches!(self, MatchMode::Fuzzy)
+    }
+}
+
+/// Score a fuzzy match of the query against the text. Every character of the query must appear
+/// in the text in order. Matches at the start of words and runs of consecutive characters score higher.
+pub fn fuzzy_score(text: &str, query: &str) -> Option<u32> {
+    let mut query_chars = query.chars().peekable();
+    let mut score = 0u32;
+    let mut previous_matched = false;
+    let mut previous_char = None;
+    for c in text.chars() {
+        let Some(next) = query_chars.peek() else {
+            break;
+        };
+        if c == *next {
+            query_chars.next();
+            score += 1;
+            if previous_matched {
+                score += 5;
+            }
+            if previous_char.is_none_or(|p: char| !p.is_alphanumeric()) {
+                score += 8;
+            }
+            previous_matched = true;
+        } else {
+            previous_matched = false;
+        }
+        previous_char = Some(c);
+    }
+    if query_chars.peek().is_some() {
+        return None;
+    }
+    // Prefer shorter texts when matches are otherwise equal.
+    let length_penalty = text.chars().count().min(u8::MAX as usize) as u32;
+    Some(score * 256 + (u8::MAX as u32 - length_penalty))
+}
+
+/// Whether the query appears in the text with no letters or digits directly before or after it.
+pub fn contains_word(text: &str, query: &str) -> bool {
+    if query.is_empty() {
+        return true;
+    }
+    text.match_indices(query)
+        .any(|(start, _)| is_word(text, start, start + query.len()))
+}
+
+/// Whether the byte range isn't directly preceded or followed by a letter or digit.
+fn is_word(text: &str, start: usize, end: usize) -> bool {
+    let before = text[..start].chars().next_back();
+    let after = text[end..].chars().next();
+    !before.is_some_and(char::is_alphanumeric) && !after.is_some_and(char::is_alphanumeric)
+}
+
+fn chars_eq(a: char, b: char, case_sensitive: bool) -> bool {
+    a == b || (!case_sensitive && a.to_lowercase().eq(b.to_lowercase()))
+}
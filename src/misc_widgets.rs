@@ -1,4 +1,4 @@
-use egui::{Color32, Frame, Stroke, TextEdit, Ui, Widget};
+use egui::{Color32, CursorIcon, Frame, Id, Label, RichText, Sense, Stroke, TextEdit, Ui, Widget};
 use rfd::FileDialog;
 
 pub fn blank_slate(ui: &mut Ui, placeholder: &str) {
@@ -7,6 +7,24 @@ pub fn blank_slate(ui: &mut Ui, placeholder: &str) {
     });
 }
 
+/// A smaller [blank_slate] which doesn't fill the available space, with an optional icon above the text
+/// and an optional button below it. Returns true if the button was clicked.
+pub fn blank_slate_with_action(
+    ui: &mut Ui,
+    icon: Option<&str>,
+    placeholder: &str,
+    action: Option<&str>,
+) -> bool {
+    ui.vertical_centered(|ui| {
+        if let Some(icon) = icon {
+            ui.label(RichText::new(icon).size(32.).weak());
+        }
+        ui.heading(placeholder);
+        action.is_some_and(|action| ui.button(action).clicked())
+    })
+    .inner
+}
+
 pub fn raised_heading(text: &str) -> impl Widget + '_ {
     move |ui: &mut Ui| {
         let fill = ui.visuals().code_bg_color;
@@ -31,3 +49,76 @@ pub fn folder_picker<'a>(value: &'a mut String, placeholder: &'a str) -> impl Wi
         .response
     }
 }
+
+/// A handle for dragging a row. Check [egui::Response::dragged] to drive reordering.
+/// Locked handles are drawn disabled and do not respond to drags.
+pub fn drag_handle(reorderable: bool) -> impl Widget {
+    move |ui: &mut Ui| {
+        let response = ui.add_enabled(reorderable, Label::new("☰").sense(Sense::drag()));
+        if reorderable {
+            response.on_hover_cursor(CursorIcon::Grab)
+        } else {
+            response
+        }
+    }
+}
+
+/// A clickable header for a group of rows showing the group name and number of rows.
+/// Returns true if the group is expanded. The collapsed state is remembered under the given ID.
+pub fn group_header(ui: &mut Ui, id: Id, text: &str, count: usize, force_open: bool) -> bool {
+    show_group_header(ui, id, text, count, force_open, false)
+}
+
+/// Like [group_header], but the collapsed state is saved with egui's memory when persistence is enabled.
+/// Only collapsed groups are stored, so expanding a group removes its entry.
+pub fn persisted_group_header(
+    ui: &mut Ui,
+    id: Id,
+    text: &str,
+    count: usize,
+    force_open: bool,
+) -> bool {
+    show_group_header(ui, id, text, count, force_open, true)
+}
+
+fn show_group_header(
+    ui: &mut Ui,
+    id: Id,
+    text: &str,
+    count: usize,
+    force_open: bool,
+    persist: bool,
+) -> bool {
+    let mut collapsed = is_group_collapsed(ui, id);
+    let icon = if collapsed && !force_open {
+        "⏵"
+    } else {
+        "⏷"
+    };
+    let label = RichText::new(format!("{} {} ({})", icon, text, count)).strong();
+    if ui.add(Label::new(label).sense(Sense::click())).clicked() && !force_open {
+        collapsed = !collapsed;
+        ui.data_mut(|data| match (collapsed, persist) {
+            (false, _) => data.remove::<bool>(id),
+            (true, true) => data.insert_persisted(id, true),
+            (true, false) => data.insert_temp(id, true),
+        });
+    }
+    force_open || !collapsed
+}
+
+/// Whether the user collapsed the [group_header] or [persisted_group_header] with the given ID.
+pub fn is_group_collapsed(ui: &Ui, id: Id) -> bool {
+    ui.data_mut(|data| data.get_persisted(id).unwrap_or_default())
+}
+
+/// Collapse or expand the group header with the given ID, ex. to reveal a row inside it.
+pub fn set_group_collapsed(ui: &Ui, id: Id, collapsed: bool) {
+    ui.data_mut(|data| {
+        if collapsed {
+            data.insert_temp(id, true);
+        } else {
+            data.remove::<bool>(id);
+        }
+    });
+}
@@ -0,0 +1,128 @@
+use std::collections::HashSet;
+
+use crate::{KeyedListModel, KeyedViewItem};
+
+/// A difference between two versions of a keyed model, as reported by [diff].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ModelChange {
+    /// The key is only in the new model. The index is in the new model.
+    Added { key: String, index: usize },
+    /// The key is only in the old model. The index is in the old model.
+    Removed { key: String, index: usize },
+    /// The item changed position relative to the other items in both models.
+    Moved { key: String, from: usize, to: usize },
+    /// The item is in both models but its contents differ. The index is in the new model.
+    Modified { key: String, index: usize },
+}
+
+impl ModelChange {
+    pub fn key(&self) -> &str {
+        match self {
+            ModelChange::Added { key, .. }
+            | ModelChange::Removed { key, .. }
+            | ModelChange::Moved { key, .. }
+            | ModelChange::Modified { key, .. } => key,
+        }
+    }
+}
+
+/// Compare two versions of a keyed model. Items with equal keys are modified if they are not equal.
+///
+/// Changes are reported as removals, then additions, then moves, then modifications.
+/// Items which only shifted because of other additions, removals, or moves are not reported as moved.
+pub fn diff<A, B, I>(old: &A, new: &B) -> Vec<ModelChange>
+where
+    A: KeyedListModel<I>,
+    B: KeyedListModel<I>,
+    I: KeyedViewItem + PartialEq,
+{
+    diff_by(old, new, |a, b| a != b)
+}
+
+/// Like [diff], but `modified` decides whether two items with the same key differ.
+pub fn diff_by<A, B, I>(old: &A, new: &B, modified: impl Fn(&I, &I) -> bool) -> Vec<ModelChange>
+where
+    A: KeyedListModel<I>,
+    B: KeyedListModel<I>,
+    I: KeyedViewItem,
+{
+    let mut changes = vec![];
+    // (old index, new index) of every key in both models, in old order.
+    let mut common = vec![];
+    for index in 0..old.len() {
+        let Some(item) = old.item(index) else {
+            continue;
+        };
+        let key = item.key();
+        match new.index_of(&key) {
+            Some(new_index) => common.push((index, new_index)),
+            None => changes.push(ModelChange::Removed {
+                key: key.into_owned(),
+                index,
+            }),
+        }
+    }
+    for index in 0..new.len() {
+        if let Some(item) = new.item(index) {
+            let key = item.key();
+            if !old.contains(&key) {
+                changes.push(ModelChange::Added {
+                    key: key.into_owned(),
+                    index,
+                });
+            }
+        }
+    }
+
+    // Items in the longest run which kept their relative order stayed put. Everything else moved.
+    let new_indices: Vec<usize> = common.iter().map(|(_, new_index)| *new_index).collect();
+    let stayed = longest_increasing_subsequence(&new_indices);
+    for (position, (from, to)) in common.iter().enumerate() {
+        if !stayed.contains(&position) {
+            if let Some(item) = new.item(*to) {
+                changes.push(ModelChange::Moved {
+                    key: item.key().into_owned(),
+                    from: *from,
+                    to: *to,
+                });
+            }
+        }
+    }
+
+    for (from, to) in common {
+        if let (Some(a), Some(b)) = (old.item(from), new.item(to)) {
+            if modified(a, b) {
+                changes.push(ModelChange::Modified {
+                    key: b.key().into_owned(),
+                    index: to,
+                });
+            }
+        }
+    }
+    changes
+}
+
+/// Positions of the values which form the longest strictly increasing subsequence.
+fn longest_increasing_subsequence(values: &[usize]) -> HashSet<usize> {
+    // tails[i] is the position of the smallest tail of an increasing run of length i + 1.
+    let mut tails: Vec<usize> = vec![];
+    let mut previous = vec![None; values.len()];
+    for (position, value) in values.iter().enumerate() {
+        let length = tails.partition_point(|tail| values[*tail] < *value);
+        if length > 0 {
+            previous[position] = Some(tails[length - 1]);
+        }
+        if length == tails.len() {
+            tails.push(position);
+        } else {
+            tails[length] = position;
+        }
+    }
+    let mut positions = HashSet::new();
+    let mut current = tails.last().copied();
+    while let Some(position) = current {
+        positions.insert(position);
+        current = previous[position];
+    }
+    positions
+}
@@ -0,0 +1,63 @@
+use std::fmt::{Display, Formatter};
+
+/// Why a checked [crate::ListModel] mutation failed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ModelError {
+    /// The index is not valid for a model with the given length.
+    OutOfBounds { index: usize, len: usize },
+    /// An item with the key already exists.
+    DuplicateKey(String),
+    /// The model doesn't support the operation, ex. removing items from a read only model.
+    Unsupported(&'static str),
+    /// The model ignored the change for a reason it didn't report.
+    Rejected,
+}
+
+impl Display for ModelError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ModelError::OutOfBounds { index, len } => {
+                write!(f, "index {} is out of bounds for length {}", index, len)
+            }
+            ModelError::DuplicateKey(key) => write!(f, "key '{}' already exists", key),
+            ModelError::Unsupported(operation) => write!(f, "{} is not supported", operation),
+            ModelError::Rejected => write!(f, "the model rejected the change"),
+        }
+    }
+}
+
+impl std::error::Error for ModelError {}
+
+/// Why [crate::KeyedListModel::rename_key] failed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RenameError {
+    /// No item has the old key.
+    NotFound(String),
+    /// Another item already has the new key.
+    Collision(String),
+    /// The new key has no characters allowed by the [crate::KeyPolicy].
+    Empty,
+    /// The model didn't accept the renamed item.
+    Rejected,
+}
+
+impl Display for RenameError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RenameError::NotFound(key) => write!(f, "key '{}' was not found", key),
+            RenameError::Collision(key) => write!(f, "key '{}' already exists", key),
+            RenameError::Empty => write!(f, "the key is empty"),
+            RenameError::Rejected => write!(f, "the model rejected the renamed item"),
+        }
+    }
+}
+
+impl std::error::Error for RenameError {}
+
+pub(crate) fn check_index(index: usize, len: usize) -> Result<(), ModelError> {
+    if index < len {
+        Ok(())
+    } else {
+        Err(ModelError::OutOfBounds { index, len })
+    }
+}
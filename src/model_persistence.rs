@@ -0,0 +1,79 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{KeyedListModel, KeyedViewItem};
+
+/// The display order of a keyed model's items, ex. an [indexmap::IndexMap] the user rearranged.
+/// Save it with any serde format and restore it after the model is reloaded.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyOrder {
+    keys: Vec<String>,
+}
+
+impl KeyOrder {
+    /// Record the current order of the model's items.
+    pub fn capture<M, I>(model: &M) -> Self
+    where
+        M: KeyedListModel<I>,
+        I: KeyedViewItem,
+    {
+        Self {
+            keys: (0..model.len())
+                .filter_map(|index| model.item(index))
+                .map(|item| item.key().into_owned())
+                .collect(),
+        }
+    }
+
+    pub fn keys(&self) -> &[String] {
+        &self.keys
+    }
+
+    /// Reorder the model to match the saved order.
+    /// Saved keys which are no longer in the model are skipped.
+    /// Items which weren't saved keep their relative order after the saved items.
+    pub fn restore<M, I>(&self, model: &mut M)
+    where
+        M: KeyedListModel<I>,
+        I: KeyedViewItem,
+    {
+        let mut target = 0;
+        for key in &self.keys {
+            let Some(index) = model.index_of(key) else {
+                continue;
+            };
+            // An index before the target means the key was saved twice.
+            if index >= target {
+                if index != target {
+                    model.move_item(index, target);
+                }
+                target += 1;
+            }
+        }
+    }
+
+    /// Keys in the model which weren't saved, ex. records added since the order was captured.
+    pub fn new_keys<M, I>(&self, model: &M) -> Vec<String>
+    where
+        M: KeyedListModel<I>,
+        I: KeyedViewItem,
+    {
+        (0..model.len())
+            .filter_map(|index| model.item(index))
+            .map(|item| item.key().into_owned())
+            .filter(|key| !self.keys.contains(key))
+            .collect()
+    }
+
+    /// Saved keys which are no longer in the model.
+    pub fn missing_keys<M, I>(&self, model: &M) -> Vec<&str>
+    where
+        M: KeyedListModel<I>,
+        I: KeyedViewItem,
+    {
+        self.keys
+            .iter()
+            .filter(|key| model.index_of(key).is_none())
+            .map(String::as_str)
+            .collect()
+    }
+}
@@ -0,0 +1,38 @@
+use std::rc::Rc;
+
+/// A saved copy of a model's items, ex. the state when a dialog opened so Cancel can discard the dialog's edits.
+/// Taken with [crate::ListModel::clone_snapshot] and applied with [crate::ListModel::restore].
+///
+/// Taking a snapshot clones every item, but the snapshot's items are shared afterwards,
+/// so cloning the snapshot itself is cheap.
+pub struct ModelSnapshot<I> {
+    items: Rc<[I]>,
+}
+
+impl<I> ModelSnapshot<I> {
+    pub(crate) fn new(items: Vec<I>) -> Self {
+        Self {
+            items: items.into(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    pub fn items(&self) -> &[I] {
+        &self.items
+    }
+}
+
+impl<I> Clone for ModelSnapshot<I> {
+    fn clone(&self) -> Self {
+        Self {
+            items: self.items.clone(),
+        }
+    }
+}
@@ -0,0 +1,118 @@
+use std::collections::HashMap;
+
+use egui::{CollapsingHeader, Grid, Ui, Widget};
+
+use crate::{KeyedListModel, KeyedViewItem};
+
+/// Summary of common data problems in a keyed model. Useful for sanity checking imported data.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ModelStats {
+    /// The number of items in the model.
+    pub total: usize,
+    /// Indices of items with empty display text.
+    pub empty_texts: Vec<usize>,
+    /// Display texts shared by more than one item, along with the indices of those items.
+    pub duplicate_texts: Vec<(String, Vec<usize>)>,
+    /// Indices of items whose key is empty or does not resolve back to the item.
+    pub invalid_keys: Vec<usize>,
+    /// Indices of items which failed validation.
+    pub validation_failures: Vec<usize>,
+}
+
+impl ModelStats {
+    /// Whether any problems were found.
+    pub fn is_healthy(&self) -> bool {
+        self.empty_texts.is_empty()
+            && self.duplicate_texts.is_empty()
+            && self.invalid_keys.is_empty()
+            && self.validation_failures.is_empty()
+    }
+}
+
+/// Collect [ModelStats] for a keyed model.
+pub fn model_stats<M, I>(model: &M) -> ModelStats
+where
+    M: KeyedListModel<I>,
+    I: KeyedViewItem,
+{
+    model_stats_with_validation(model, |_| true)
+}
+
+/// Collect [ModelStats] for a keyed model. Items where `validate` returns false are counted as validation failures.
+pub fn model_stats_with_validation<M, I>(model: &M, validate: impl Fn(&I) -> bool) -> ModelStats
+where
+    M: KeyedListModel<I>,
+    I: KeyedViewItem,
+{
+    let mut stats = ModelStats {
+        total: model.len(),
+        ..Default::default()
+    };
+    let mut texts: HashMap<String, Vec<usize>> = HashMap::new();
+    for (i, item) in model.iter_indexed() {
+        let text = item.with_text(|text| text.to_string());
+        if text.is_empty() {
+            stats.empty_texts.push(i);
+        } else {
+            texts.entry(text).or_default().push(i);
+        }
+        let key = item.key();
+        if key.is_empty() || model.index_of(&key) != Some(i) {
+            stats.invalid_keys.push(i);
+        }
+        if !validate(item) {
+            stats.validation_failures.push(i);
+        }
+    }
+    stats.duplicate_texts = texts
+        .into_iter()
+        .filter(|(_, indices)| indices.len() > 1)
+        .collect();
+    stats.duplicate_texts.sort_by_key(|(_, indices)| indices[0]);
+    stats
+}
+
+fn index_list(ui: &mut Ui, label: &str, indices: &[usize]) {
+    CollapsingHeader::new(format!("{} ({})", label, indices.len()))
+        .enabled(!indices.is_empty())
+        .show(ui, |ui| {
+            for index in indices {
+                ui.label(format!("#{}", index + 1));
+            }
+        });
+}
+
+/// A panel summarizing [ModelStats].
+pub fn model_stats_report(stats: &ModelStats) -> impl Widget + '_ {
+    move |ui: &mut Ui| {
+        ui.vertical(|ui| {
+            Grid::new(ui.auto_id_with("__thane_model_stats"))
+                .num_columns(2)
+                .show(ui, |ui| {
+                    ui.label("Items");
+                    ui.label(stats.total.to_string());
+                    ui.end_row();
+                    ui.label("Status");
+                    if stats.is_healthy() {
+                        ui.label("No problems found");
+                    } else {
+                        ui.colored_label(ui.visuals().warn_fg_color, "Problems found");
+                    }
+                    ui.end_row();
+                });
+            index_list(ui, "Empty text", &stats.empty_texts);
+            CollapsingHeader::new(format!("Duplicate text ({})", stats.duplicate_texts.len()))
+                .enabled(!stats.duplicate_texts.is_empty())
+                .show(ui, |ui| {
+                    for (text, indices) in &stats.duplicate_texts {
+                        let rows: Vec<String> =
+                            indices.iter().map(|i| format!("#{}", i + 1)).collect();
+                        ui.label(format!("{}: {}", text, rows.join(", ")));
+                    }
+                });
+            index_list(ui, "Invalid keys", &stats.invalid_keys);
+            index_list(ui, "Validation failures", &stats.validation_failures);
+        })
+        .response
+    }
+}
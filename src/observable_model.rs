@@ -0,0 +1,284 @@
+use crate::{KeyedListModel, ListModel, ModelError};
+
+/// A change made to a [ListModel]. Indices refer to the model at the time of the change.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModelEvent {
+    /// An item was inserted at the index.
+    Inserted(usize),
+    /// The item at the index was removed.
+    Removed(usize),
+    /// The item at `from` was moved to `to`, shifting the items in between.
+    Moved { from: usize, to: usize },
+    /// The items at the two indices exchanged positions.
+    Swapped(usize, usize),
+    /// The item at the index may have been modified.
+    Changed(usize),
+    /// The model changed in a way that can't be described incrementally.
+    Reset,
+}
+
+impl ModelEvent {
+    /// Where an item at the given index before the event is afterwards. Returns [None] if the item is gone
+    /// or the event is a [ModelEvent::Reset].
+    pub fn map_index(&self, index: usize) -> Option<usize> {
+        match *self {
+            ModelEvent::Inserted(i) => Some(if index >= i { index + 1 } else { index }),
+            ModelEvent::Removed(i) => match index.cmp(&i) {
+                std::cmp::Ordering::Less => Some(index),
+                std::cmp::Ordering::Equal => None,
+                std::cmp::Ordering::Greater => Some(index - 1),
+            },
+            ModelEvent::Moved { from, to } => Some(if index == from {
+                to
+            } else if from < to && index > from && index <= to {
+                index - 1
+            } else if to < from && index >= to && index < from {
+                index + 1
+            } else {
+                index
+            }),
+            ModelEvent::Swapped(a, b) => Some(if index == a {
+                b
+            } else if index == b {
+                a
+            } else {
+                index
+            }),
+            ModelEvent::Changed(_) => Some(index),
+            ModelEvent::Reset => None,
+        }
+    }
+}
+
+/// Remembers the [ListModel::generation] a cache was built from, ex. a proxy's row map.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct InvalidationHandle {
+    generation: Option<u64>,
+}
+
+impl InvalidationHandle {
+    /// Whether the model changed since the handle was last updated.
+    /// Always false for models which don't report a generation.
+    pub fn is_stale<M, I>(&self, model: &M) -> bool
+    where
+        M: ListModel<I>,
+    {
+        let generation = model.generation();
+        generation.is_some() && generation != self.generation
+    }
+
+    /// Record the model's current generation. Returns whether the model changed since the last update.
+    pub fn update<M, I>(&mut self, model: &M) -> bool
+    where
+        M: ListModel<I>,
+    {
+        let stale = self.is_stale(model);
+        self.generation = model.generation();
+        stale
+    }
+
+    /// Forget the recorded generation so the next update reports a change.
+    pub fn invalidate(&mut self) {
+        self.generation = None;
+    }
+}
+
+/// Wraps a [ListModel] and records every change made through it as a [ModelEvent].
+/// Proxies, caches, and widgets can drain the events to update incrementally instead of rebuilding every frame.
+///
+/// Every recorded change also advances the model's [ListModel::generation], so proxies rebuild automatically.
+/// Changes made with [ListModel::item_mut] are NOT recorded. Use [ObservableModel::edit] or [ObservableModel::notify] instead.
+pub struct ObservableModel<M> {
+    model: M,
+    events: Vec<ModelEvent>,
+    generation: u64,
+}
+
+impl<M> ObservableModel<M> {
+    pub fn new(model: M) -> Self {
+        Self {
+            model,
+            events: vec![],
+            generation: 0,
+        }
+    }
+
+    /// Access the wrapped model. Changes made directly to it are NOT recorded.
+    pub fn inner(&self) -> &M {
+        &self.model
+    }
+
+    /// Mutably access the wrapped model. Records a [ModelEvent::Reset] since the changes can't be tracked.
+    pub fn inner_mut(&mut self) -> &mut M {
+        self.record(ModelEvent::Reset);
+        &mut self.model
+    }
+
+    pub fn into_inner(self) -> M {
+        self.model
+    }
+
+    /// Events recorded since the last call to [ObservableModel::take_events].
+    pub fn events(&self) -> &[ModelEvent] {
+        &self.events
+    }
+
+    /// Remove and return the recorded events.
+    pub fn take_events(&mut self) -> Vec<ModelEvent> {
+        std::mem::take(&mut self.events)
+    }
+
+    /// Record a change that was made outside of the [ListModel] API.
+    pub fn notify(&mut self, event: ModelEvent) {
+        self.record(event);
+    }
+
+    /// Modify the item at the given index. `edit` returns whether it changed the item,
+    /// and only changes are recorded as a [ModelEvent::Changed]. Returns false if nothing changed.
+    pub fn edit<I>(&mut self, index: usize, edit: impl FnOnce(&mut I) -> bool) -> bool
+    where
+        M: ListModel<I>,
+    {
+        let changed = self.model.item_mut(index).is_some_and(edit);
+        if changed {
+            self.record(ModelEvent::Changed(index));
+        }
+        changed
+    }
+
+    fn record(&mut self, event: ModelEvent) {
+        self.events.push(event);
+        self.generation = self.generation.wrapping_add(1);
+    }
+}
+
+impl<M, I> ListModel<I> for ObservableModel<M>
+where
+    M: ListModel<I>,
+{
+    fn is_empty(&self) -> bool {
+        self.model.is_empty()
+    }
+
+    fn len(&self) -> usize {
+        self.model.len()
+    }
+
+    fn item(&self, index: usize) -> Option<&I> {
+        self.model.item(index)
+    }
+
+    fn item_mut(&mut self, index: usize) -> Option<&mut I> {
+        self.model.item_mut(index)
+    }
+
+    fn add(&mut self, item: I) {
+        let len = self.model.len();
+        self.model.add(item);
+        if self.model.len() > len {
+            self.record(ModelEvent::Inserted(len));
+        }
+    }
+
+    fn clear(&mut self) {
+        if !self.model.is_empty() {
+            self.model.clear();
+            self.record(ModelEvent::Reset);
+        }
+    }
+
+    fn insert(&mut self, index: usize, item: I) {
+        let len = self.model.len();
+        self.model.insert(index, item);
+        if self.model.len() > len {
+            self.record(ModelEvent::Inserted(index));
+        }
+    }
+
+    fn remove(&mut self, index: usize) {
+        let len = self.model.len();
+        self.model.remove(index);
+        if self.model.len() < len {
+            self.record(ModelEvent::Removed(index));
+        }
+    }
+
+    fn swap_items(&mut self, a: usize, b: usize) {
+        self.model.swap_items(a, b);
+        if a < self.model.len() && b < self.model.len() && a != b {
+            self.record(ModelEvent::Swapped(a, b));
+        }
+    }
+
+    fn copy(&mut self, a: usize, b: usize) {
+        self.model.copy(a, b);
+        if a < self.model.len() && b < self.model.len() {
+            self.record(ModelEvent::Changed(b));
+        }
+    }
+
+    fn move_item(&mut self, from: usize, to: usize) {
+        self.model.move_item(from, to);
+        if from < self.model.len() && to < self.model.len() && from != to {
+            self.record(ModelEvent::Moved { from, to });
+        }
+    }
+
+    fn row_to_index(&self, row_number: usize) -> Option<usize> {
+        self.model.row_to_index(row_number)
+    }
+
+    fn generation(&self) -> Option<u64> {
+        Some(self.generation)
+    }
+
+    fn try_add(&mut self, item: I) -> Result<(), ModelError> {
+        let index = self.model.len();
+        self.model.try_add(item)?;
+        self.record(ModelEvent::Inserted(index));
+        Ok(())
+    }
+
+    fn try_insert(&mut self, index: usize, item: I) -> Result<(), ModelError> {
+        self.model.try_insert(index, item)?;
+        self.record(ModelEvent::Inserted(index));
+        Ok(())
+    }
+
+    fn try_remove(&mut self, index: usize) -> Result<(), ModelError> {
+        self.model.try_remove(index)?;
+        self.record(ModelEvent::Removed(index));
+        Ok(())
+    }
+
+    fn try_swap_items(&mut self, a: usize, b: usize) -> Result<(), ModelError> {
+        self.model.try_swap_items(a, b)?;
+        if a != b {
+            self.record(ModelEvent::Swapped(a, b));
+        }
+        Ok(())
+    }
+
+    fn try_move_item(&mut self, from: usize, to: usize) -> Result<(), ModelError> {
+        self.model.try_move_item(from, to)?;
+        if from != to {
+            self.record(ModelEvent::Moved { from, to });
+        }
+        Ok(())
+    }
+
+    fn try_copy(&mut self, a: usize, b: usize) -> Result<(), ModelError> {
+        self.model.try_copy(a, b)?;
+        self.record(ModelEvent::Changed(b));
+        Ok(())
+    }
+}
+
+impl<M, I> KeyedListModel<I> for ObservableModel<M>
+where
+    M: KeyedListModel<I>,
+{
+    fn index_of(&self, key: &str) -> Option<usize> {
+        self.model.index_of(key)
+    }
+}
@@ -0,0 +1,194 @@
+use std::ops::Range;
+use std::sync::mpsc::Receiver;
+
+use crate::virtual_list_model::PageSource;
+use crate::ListModel;
+
+/// A [ListModel] which grows a page at a time, ex. results from a paginated backend whose total length is unknown.
+/// Loading stops once the backend returns a page with fewer than `page_size` items.
+///
+/// See [crate::InfiniteScroll] for a widget which requests pages as the user scrolls.
+pub struct PagedListModel<I> {
+    items: Vec<I>,
+    page_size: usize,
+    fetched: usize,
+    has_more: bool,
+    loading: bool,
+    source: PageSource<I>,
+    generation: u64,
+}
+
+impl<I> PagedListModel<I> {
+    /// Create a model which loads pages synchronously with the given callback.
+    /// The callback receives the range of indices to load.
+    pub fn new(page_size: usize, fetch: impl FnMut(Range<usize>) -> Vec<I> + 'static) -> Self {
+        Self::with_source(page_size, PageSource::Sync(Box::new(fetch)))
+    }
+
+    /// Create a model which loads pages in the background. `request` receives the page number and
+    /// range of indices to load, and the loaded page should be sent back through `receiver`.
+    pub fn with_channel(
+        page_size: usize,
+        request: impl FnMut(usize, Range<usize>) + 'static,
+        receiver: Receiver<(usize, Vec<I>)>,
+    ) -> Self {
+        Self::with_source(
+            page_size,
+            PageSource::Channel {
+                request: Box::new(request),
+                receiver,
+            },
+        )
+    }
+
+    fn with_source(page_size: usize, source: PageSource<I>) -> Self {
+        Self {
+            items: vec![],
+            page_size: page_size.max(1),
+            fetched: 0,
+            has_more: true,
+            loading: false,
+            source,
+            generation: 0,
+        }
+    }
+
+    /// Whether the backend may have more items.
+    pub fn has_more(&self) -> bool {
+        self.has_more
+    }
+
+    /// Whether a page has been requested but not received yet.
+    pub fn is_loading(&self) -> bool {
+        self.loading
+    }
+
+    /// Discard every loaded item and start over from the first page.
+    pub fn reset(&mut self) {
+        self.items.clear();
+        self.fetched = 0;
+        self.has_more = true;
+        self.loading = false;
+        self.bump();
+    }
+
+    /// Request the next page. Does nothing if a page is already loading or there are no more items.
+    pub fn request_next_page(&mut self) {
+        if self.loading || !self.has_more {
+            return;
+        }
+        let range = self.fetched..self.fetched + self.page_size;
+        match &mut self.source {
+            PageSource::Sync(fetch) => {
+                let items = fetch(range);
+                self.receive(items);
+            }
+            PageSource::Channel { request, .. } => {
+                request(self.fetched / self.page_size, range);
+                self.loading = true;
+            }
+        }
+    }
+
+    /// Receive pages loaded in the background. Returns true if a page was loaded.
+    pub fn update(&mut self) -> bool {
+        let mut pages = vec![];
+        if let PageSource::Channel { receiver, .. } = &self.source {
+            while let Ok((page, items)) = receiver.try_recv() {
+                // Ignore pages requested before the model was reset.
+                if self.loading && page == self.fetched / self.page_size {
+                    pages.push(items);
+                }
+            }
+        }
+        let loaded = !pages.is_empty();
+        for items in pages {
+            self.receive(items);
+        }
+        loaded
+    }
+
+    fn receive(&mut self, items: Vec<I>) {
+        self.loading = false;
+        self.has_more = items.len() >= self.page_size;
+        self.fetched += items.len();
+        self.items.extend(items);
+        self.bump();
+    }
+
+    fn bump(&mut self) {
+        self.generation = self.generation.wrapping_add(1);
+    }
+}
+
+impl<I> ListModel<I> for PagedListModel<I>
+where
+    I: Clone,
+{
+    fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    fn item(&self, index: usize) -> Option<&I> {
+        self.items.get(index)
+    }
+
+    fn item_mut(&mut self, index: usize) -> Option<&mut I> {
+        self.items.get_mut(index)
+    }
+
+    fn add(&mut self, item: I) {
+        self.items.push(item);
+        self.bump();
+    }
+
+    fn insert(&mut self, index: usize, item: I) {
+        if index <= self.items.len() {
+            self.items.insert(index, item);
+            self.bump();
+        }
+    }
+
+    fn remove(&mut self, index: usize) {
+        if index < self.items.len() {
+            self.items.remove(index);
+            self.bump();
+        }
+    }
+
+    fn swap_items(&mut self, a: usize, b: usize) {
+        if a < self.items.len() && b < self.items.len() {
+            self.items.swap(a, b);
+            self.bump();
+        }
+    }
+
+    fn copy(&mut self, a: usize, b: usize) {
+        if a < self.items.len() && b < self.items.len() {
+            self.items[b] = self.items[a].clone();
+            self.bump();
+        }
+    }
+
+    fn move_item(&mut self, from: usize, to: usize) {
+        if from < self.items.len() && to < self.items.len() {
+            let item = self.items.remove(from);
+            self.items.insert(to, item);
+            self.bump();
+        }
+    }
+
+    fn row_to_index(&self, row_number: usize) -> Option<usize> {
+        (row_number < self.items.len()).then_some(row_number)
+    }
+
+    /// Advances when a page arrives, the model is reset, or items are added, removed, or moved.
+    /// Changes made with [ListModel::item_mut] don't advance it.
+    fn generation(&self) -> Option<u64> {
+        Some(self.generation)
+    }
+}
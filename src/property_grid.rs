@@ -1,13 +1,25 @@
 use std::collections::HashSet;
 
-use egui::{Grid, Id, Label, Response, ScrollArea, TextEdit, Ui};
+use egui::emath::Numeric;
+use egui::{Grid, Id, Label, Response, ScrollArea, Sense, TextEdit, Ui};
 
 use crate::raised_heading;
+use crate::row_layout::modified_marker;
 
-use crate::DefaultWidget;
+use crate::{
+    converted_drag_value, DefaultWidget, KeyedListModel, KeyedViewItem, ModelDropDown,
+    UnitConversion,
+};
 
 type FieldRenderer<'a, D> = Box<dyn Fn(&mut Ui, &mut D) -> Response + 'a>;
 
+/// How the user switches a row from its display widget to its editor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EditTrigger {
+    Click,
+    DoubleClick,
+}
+
 struct PropertyGridSection<'a, D> {
     name: &'a str,
     num_columns: usize,
@@ -42,7 +54,13 @@ impl<'a, D> PropertyGridSection<'a, D> {
                 .any(|label| label.to_lowercase().contains(&filter.to_lowercase()))
     }
 
-    pub fn show(&self, ui: &mut Ui, data: &mut D, filter: &str) -> Response {
+    pub fn show(
+        &self,
+        ui: &mut Ui,
+        data: &mut D,
+        filter: &str,
+        modified: Option<&dyn Fn(&str) -> bool>,
+    ) -> Response {
         if !self.name.is_empty() {
             ui.add(raised_heading(self.name));
         }
@@ -53,9 +71,21 @@ impl<'a, D> PropertyGridSection<'a, D> {
                 let mut fields_in_row = 0;
                 for (label, add_contents) in &self.fields {
                     if label.to_lowercase().contains(&filter.to_lowercase()) {
-                        ui.vertical(|ui| {
-                            ui.add(Label::new(*label).extend());
-                        });
+                        match modified {
+                            Some(modified) => {
+                                ui.horizontal(|ui| {
+                                    ui.add(Label::new(*label).extend());
+                                    if modified(label) {
+                                        modified_marker(ui);
+                                    }
+                                });
+                            }
+                            None => {
+                                ui.vertical(|ui| {
+                                    ui.add(Label::new(*label).extend());
+                                });
+                            }
+                        }
                         if add_contents(ui, data).changed() {
                             changed = true;
                         }
@@ -82,6 +112,7 @@ pub struct PropertyGrid<'a, D> {
     id: Id,
     data: &'a mut D,
     sections: Vec<PropertyGridSection<'a, D>>,
+    modified: Option<&'a dyn Fn(&str) -> bool>,
 }
 
 impl<'a, D> PropertyGrid<'a, D> {
@@ -89,10 +120,17 @@ impl<'a, D> PropertyGrid<'a, D> {
         Self {
             data,
             sections: vec![],
+            modified: None,
             id: Id::new(id_source).with("property_grid"),
         }
     }
 
+    /// Mark fields which have unsaved changes. The callback receives the field's label.
+    pub fn modified(mut self, modified: &'a dyn Fn(&str) -> bool) -> Self {
+        self.modified = Some(modified);
+        self
+    }
+
     pub fn new_section(mut self, name: &'a str) -> Self {
         self.sections.push(PropertyGridSection::new(name, 1));
         self
@@ -115,6 +153,42 @@ impl<'a, D> PropertyGrid<'a, D> {
         self
     }
 
+    /// Add a field which renders `view` until the user starts editing it, then switches to `edit`.
+    /// Editing stops when the editor loses focus or the user clicks elsewhere.
+    pub fn view_edit_field(
+        mut self,
+        label: &'a str,
+        trigger: EditTrigger,
+        view: impl Fn(&mut Ui, &D) -> Response + 'a,
+        edit: impl Fn(&mut Ui, &mut D) -> Response + 'a,
+    ) -> Self {
+        if let Some(section) = self.sections.last_mut() {
+            let id = self.id.with(section.name).with(label);
+            section.field(label, move |ui: &mut Ui, data: &mut D| {
+                let editing = ui.data(|mem| mem.get_temp::<bool>(id).unwrap_or_default());
+                if editing {
+                    let response = edit(ui, data);
+                    if response.lost_focus() || response.clicked_elsewhere() {
+                        ui.data_mut(|mem| mem.remove::<bool>(id));
+                    }
+                    response
+                } else {
+                    let response = view(ui, data).interact(Sense::click());
+                    let triggered = match trigger {
+                        EditTrigger::Click => response.clicked(),
+                        EditTrigger::DoubleClick => response.double_clicked(),
+                    };
+                    if triggered {
+                        ui.data_mut(|mem| mem.insert_temp(id, true));
+                        ui.ctx().request_repaint();
+                    }
+                    response
+                }
+            })
+        }
+        self
+    }
+
     pub fn default_field<F>(
         mut self,
         label: &'a str,
@@ -131,6 +205,51 @@ impl<'a, D> PropertyGrid<'a, D> {
         self
     }
 
+    /// Add a numeric field which is displayed and edited in a different unit than it is stored in.
+    pub fn converted_field<N>(
+        mut self,
+        label: &'a str,
+        conversion: UnitConversion,
+        retrieve_field: impl Fn(&mut D) -> &mut N + 'a,
+    ) -> Self
+    where
+        N: Numeric,
+    {
+        if let Some(section) = self.sections.last_mut() {
+            section.field(label, move |ui: &mut Ui, data: &mut D| {
+                ui.add(converted_drag_value(retrieve_field(data), conversion))
+            })
+        }
+        self
+    }
+
+    /// Add a field which references a record in a keyed model by its key.
+    /// The row has a button which emits a [crate::NavigationEvent] for `model_id` and the selected key.
+    pub fn reference_field<M, I, DD>(
+        mut self,
+        label: &'a str,
+        model_id: &'a str,
+        model: &'a M,
+        decoration_dependencies: &'a DD,
+        retrieve_field: impl Fn(&mut D) -> &mut Option<String> + 'a,
+    ) -> Self
+    where
+        M: KeyedListModel<I>,
+        I: KeyedViewItem<DecorationDependencies = DD>,
+    {
+        if let Some(section) = self.sections.last_mut() {
+            section.field(label, move |ui: &mut Ui, data: &mut D| {
+                ModelDropDown::default().go_to_definition(model_id).show(
+                    ui,
+                    model,
+                    decoration_dependencies,
+                    retrieve_field(data),
+                )
+            })
+        }
+        self
+    }
+
     pub fn show(&mut self, ui: &mut Ui) -> Response {
         let mut filter: String = ui.memory_mut(|mem| {
             std::mem::take(mem.data.get_persisted_mut_or_default::<String>(self.id))
@@ -144,7 +263,9 @@ impl<'a, D> PropertyGrid<'a, D> {
                     ui.separator();
                     for section in &self.sections {
                         if section.visible(&filter)
-                            && section.show(ui, self.data, &filter).changed()
+                            && section
+                                .show(ui, self.data, &filter, self.modified)
+                                .changed()
                         {
                             changed = true;
                         }
@@ -0,0 +1,358 @@
+use std::borrow::Cow;
+
+use egui::text::TextWrapping;
+use egui::{
+    Align, Checkbox, FontSelection, Frame, Id, Layout, Margin, Response, RichText, Rounding,
+    TextStyle, Ui, WidgetText,
+};
+
+use crate::validation::validation_badge;
+use crate::{DecorationKind, ItemStyle, ListModel, ValidationError, ViewItem};
+
+type TrailingRenderer<'a> = &'a dyn Fn(&mut Ui, usize);
+
+/// How row text behaves when it is wider than the row.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TextOverflow {
+    /// Widen the row to fit the text.
+    #[default]
+    Extend,
+    /// Cut the text off with "…" and show the full text in a tooltip.
+    Truncate,
+    /// Wrap the text onto at most the given number of lines.
+    Wrap(usize),
+}
+
+/// Describes how a row is laid out in model widgets.
+/// Each slot is rendered in its own cell so rows line up when shown in a [egui::Grid].
+///
+/// Callbacks receive the index of the item in the model being displayed.
+pub struct RowLayout<'a> {
+    show_decoration: bool,
+    subtitle: Option<&'a dyn Fn(usize) -> Option<String>>,
+    badges: Option<&'a dyn Fn(usize) -> Vec<String>>,
+    trailing: Option<TrailingRenderer<'a>>,
+    height: Option<f32>,
+    text_overflow: TextOverflow,
+    max_width: Option<f32>,
+    modified: Option<&'a dyn Fn(usize) -> bool>,
+    validation: Option<&'a dyn Fn(usize) -> Vec<ValidationError>>,
+}
+
+impl Default for RowLayout<'_> {
+    fn default() -> Self {
+        Self {
+            show_decoration: true,
+            subtitle: None,
+            badges: None,
+            trailing: None,
+            height: None,
+            text_overflow: TextOverflow::Extend,
+            max_width: None,
+            modified: None,
+            validation: None,
+        }
+    }
+}
+
+impl<'a> RowLayout<'a> {
+    /// Whether to reserve a slot for the item's decoration (if the item type is decorated).
+    pub fn decoration(mut self, show_decoration: bool) -> Self {
+        self.show_decoration = show_decoration;
+        self
+    }
+
+    /// Secondary text displayed below the item text. Takes priority over [ViewItem::with_subtitle].
+    pub fn subtitle(mut self, subtitle: &'a dyn Fn(usize) -> Option<String>) -> Self {
+        self.subtitle = Some(subtitle);
+        self
+    }
+
+    /// Short labels displayed after the item text.
+    pub fn badges(mut self, badges: &'a dyn Fn(usize) -> Vec<String>) -> Self {
+        self.badges = Some(badges);
+        self
+    }
+
+    /// Extra widgets displayed at the end of the row.
+    pub fn trailing(mut self, trailing: TrailingRenderer<'a>) -> Self {
+        self.trailing = Some(trailing);
+        self
+    }
+
+    /// The height of every row. Rows are given exactly this height, whatever their contents.
+    pub fn height(mut self, height: f32) -> Self {
+        self.height = Some(height);
+        self
+    }
+
+    /// How to handle text which is wider than the row. [crate::EditableList] rows, whose text comes from
+    /// the item renderer, apply it to subtitles and to renderers which use [RowLayout::label].
+    pub fn text_overflow(mut self, text_overflow: TextOverflow) -> Self {
+        self.text_overflow = text_overflow;
+        self
+    }
+
+    /// The maximum width of the text slot, or of the item renderer in a [crate::EditableList].
+    /// Defaults to the available width.
+    pub fn max_width(mut self, max_width: f32) -> Self {
+        self.max_width = Some(max_width);
+        self
+    }
+
+    /// Mark rows which have unsaved changes, ex. using [crate::DirtyTrackingModel::is_dirty].
+    pub fn modified(mut self, modified: &'a dyn Fn(usize) -> bool) -> Self {
+        self.modified = Some(modified);
+        self
+    }
+
+    /// Show a warning badge on rows with problems, ex. using [crate::ValidatedModel::errors].
+    pub fn validation(mut self, validation: &'a dyn Fn(usize) -> Vec<ValidationError>) -> Self {
+        self.validation = Some(validation);
+        self
+    }
+
+    /// The number of grid columns a row occupies.
+    pub fn num_columns(&self, decorated: bool) -> usize {
+        1 + usize::from(self.show_decoration && decorated)
+            + usize::from(self.badges.is_some())
+            + usize::from(self.trailing.is_some())
+    }
+
+    /// Render the row for an item. Does NOT end the grid row.
+    /// Returns the response of the item's text label.
+    pub fn show_item<M, I, DD>(
+        &self,
+        ui: &mut Ui,
+        model: &M,
+        decoration_dependencies: &DD,
+        kind: DecorationKind<'_>,
+        index: usize,
+        selected: bool,
+    ) -> Response
+    where
+        M: ListModel<I>,
+        I: ViewItem<DecorationDependencies = DD>,
+    {
+        self.show_item_with_id(
+            ui,
+            model,
+            decoration_dependencies,
+            kind,
+            index,
+            selected,
+            I::stable_id,
+        )
+    }
+
+    /// Like [RowLayout::show_item], with the ID used to salt the row's contents.
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn show_item_with_id<M, I, DD>(
+        &self,
+        ui: &mut Ui,
+        model: &M,
+        decoration_dependencies: &DD,
+        kind: DecorationKind<'_>,
+        index: usize,
+        selected: bool,
+        stable_id: fn(&I) -> Option<Id>,
+    ) -> Response
+    where
+        M: ListModel<I>,
+        I: ViewItem<DecorationDependencies = DD>,
+    {
+        let item = model.item(index);
+        if self.show_decoration && I::decorated(kind) {
+            match item {
+                Some(item) => {
+                    item.with_decoration(decoration_dependencies, kind, |image| match image {
+                        Some(image) => ui.add(image),
+                        None => ui.label(""),
+                    })
+                }
+                None => ui.label(""),
+            };
+        }
+        let item_subtitle = item.and_then(|item| item.with_subtitle(|s| s.map(str::to_string)));
+        let row_id = item.and_then(stable_id).unwrap_or_else(|| Id::new(index));
+        let item_badge = item.and_then(|item| item.badge().map(Cow::into_owned));
+        let response = self.show_text(
+            ui,
+            index,
+            row_id,
+            item_subtitle,
+            item_badge,
+            |ui| match item {
+                Some(item) => item.with_text(|text| {
+                    let (widget_text, elided) = self.row_text(ui, text, item.style_hint());
+                    let response = ui.selectable_label(selected, widget_text);
+                    item.with_tooltip(|tooltip| match (elided, tooltip) {
+                        (true, Some(tooltip)) => {
+                            response.on_hover_text(format!("{}\n\n{}", text, tooltip))
+                        }
+                        (true, None) => response.on_hover_text(text),
+                        (false, Some(tooltip)) => response.on_hover_text(tooltip),
+                        (false, None) => response,
+                    })
+                }),
+                // Out of bounds - fill with empty space.
+                None => ui.label(""),
+            },
+        );
+        self.show_extras(ui, index);
+        response
+    }
+
+    /// Render the check box slot of a checkable item (see [ViewItem::is_checked]).
+    pub(crate) fn show_check(&self, ui: &mut Ui, checked: &mut bool, enabled: bool) -> Response {
+        ui.add_enabled(enabled, Checkbox::without_text(checked))
+    }
+
+    /// A label which follows the overflow settings, showing the full text on hover if it was elided.
+    /// Use it in item renderers, ex. for the rows of a read-only [crate::EditableList].
+    pub fn label(&self, ui: &mut Ui, text: impl Into<RichText>) -> Response {
+        let rich_text = text.into();
+        let full_text = rich_text.text().to_string();
+        let (widget_text, elided) = self.layout_text(ui, rich_text);
+        let response = ui.label(widget_text);
+        if elided {
+            response.on_hover_text(full_text)
+        } else {
+            response
+        }
+    }
+
+    /// Lay out item text according to the overflow settings. Returns whether the text was elided.
+    pub(crate) fn row_text(&self, ui: &Ui, text: &str, style: ItemStyle) -> (WidgetText, bool) {
+        self.layout_text(ui, style.apply(text, ui.visuals()))
+    }
+
+    fn layout_text(&self, ui: &Ui, rich_text: RichText) -> (WidgetText, bool) {
+        let max_rows = match self.text_overflow {
+            TextOverflow::Extend => return (rich_text.into(), false),
+            TextOverflow::Truncate => 1,
+            TextOverflow::Wrap(max_rows) => max_rows.max(1),
+        };
+        let mut job = WidgetText::from(rich_text).into_layout_job(
+            ui.style(),
+            FontSelection::Style(TextStyle::Button),
+            Align::Center,
+        );
+        job.wrap = TextWrapping {
+            max_width: self.max_width.unwrap_or_else(|| ui.available_width()),
+            max_rows,
+            ..Default::default()
+        };
+        let galley = ui.fonts(|fonts| fonts.layout_job(job));
+        let elided = galley.elided;
+        (galley.into(), elided)
+    }
+
+    pub(crate) fn shows_decoration(&self) -> bool {
+        self.show_decoration
+    }
+
+    pub(crate) fn fixed_height(&self) -> Option<f32> {
+        self.height
+    }
+
+    pub(crate) fn text_max_width(&self) -> Option<f32> {
+        self.max_width
+    }
+
+    pub(crate) fn subtitle_text(&self, index: usize) -> Option<String> {
+        self.subtitle.and_then(|subtitle| subtitle(index))
+    }
+
+    pub(crate) fn is_modified(&self, index: usize) -> bool {
+        self.modified.is_some_and(|modified| modified(index))
+    }
+
+    pub(crate) fn errors(&self, index: usize) -> Vec<ValidationError> {
+        self.validation
+            .map(|validation| validation(index))
+            .unwrap_or_default()
+    }
+
+    /// Render the text slot using a custom widget, followed by the subtitle (if any).
+    fn show_text(
+        &self,
+        ui: &mut Ui,
+        index: usize,
+        row_id: Id,
+        item_subtitle: Option<String>,
+        item_badge: Option<String>,
+        add_contents: impl FnOnce(&mut Ui) -> Response,
+    ) -> Response {
+        // Salt the row's contents so their state follows the item when rows are reordered.
+        ui.push_id(row_id, |ui| {
+            ui.vertical(|ui| {
+                if let Some(height) = self.height {
+                    ui.set_height(height);
+                }
+                if let Some(max_width) = self.max_width {
+                    ui.set_max_width(max_width);
+                }
+                let modified = self.is_modified(index);
+                let errors = self.errors(index);
+                let response = if modified || !errors.is_empty() || item_badge.is_some() {
+                    ui.horizontal(|ui| {
+                        let response = add_contents(ui);
+                        if modified {
+                            modified_marker(ui);
+                        }
+                        validation_badge(ui, &errors);
+                        if let Some(badge) = item_badge {
+                            ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+                                item_badge_pill(ui, &badge)
+                            });
+                        }
+                        response
+                    })
+                    .inner
+                } else {
+                    add_contents(ui)
+                };
+                if let Some(subtitle) = self.subtitle_text(index).or(item_subtitle) {
+                    ui.label(RichText::new(subtitle).small().weak());
+                }
+                response
+            })
+            .inner
+        })
+        .inner
+    }
+
+    /// Render the badge and trailing slots.
+    pub(crate) fn show_extras(&self, ui: &mut Ui, index: usize) {
+        if let Some(badges) = self.badges {
+            ui.horizontal(|ui| {
+                for badge in badges(index) {
+                    Frame::group(ui.style())
+                        .inner_margin(2.)
+                        .show(ui, |ui| ui.small(badge));
+                }
+            });
+        }
+        if let Some(trailing) = self.trailing {
+            ui.horizontal(|ui| trailing(ui, index));
+        }
+    }
+}
+
+/// A subdued pill showing an item's [ViewItem::badge].
+pub(crate) fn item_badge_pill(ui: &mut Ui, text: &str) -> Response {
+    Frame::none()
+        .fill(ui.visuals().faint_bg_color)
+        .rounding(Rounding::same(8.))
+        .inner_margin(Margin::symmetric(6., 1.))
+        .show(ui, |ui| ui.label(RichText::new(text).small().weak()))
+        .response
+}
+
+/// A small dot indicating that a row or field has unsaved changes.
+pub(crate) fn modified_marker(ui: &mut Ui) -> Response {
+    let color = ui.visuals().warn_fg_color;
+    ui.label(RichText::new("●").small().color(color))
+        .on_hover_text("Modified")
+}
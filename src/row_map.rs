@@ -0,0 +1,189 @@
+use std::ops::Range;
+
+const NO_ROW: usize = usize::MAX;
+
+/// Maps rows of a proxy to indices in its source model and back in constant time.
+/// Proxies over very large models use it so looking up the row of a source item doesn't scan every row.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RowMap {
+    rows: Vec<usize>,
+    // Source index -> row, or NO_ROW if the source item isn't shown.
+    inverse: Vec<usize>,
+}
+
+impl RowMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Build a map from the source index of every row.
+    pub fn from_rows(rows: Vec<usize>) -> Self {
+        let mut map = Self {
+            rows,
+            inverse: vec![],
+        };
+        map.rebuild_inverse();
+        map
+    }
+
+    /// Every source index in row order.
+    pub fn rows(&self) -> &[usize] {
+        &self.rows
+    }
+
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.rows.clear();
+        self.inverse.clear();
+    }
+
+    /// Add a row showing the source item at the given index.
+    pub fn push(&mut self, source_index: usize) {
+        if source_index >= self.inverse.len() {
+            self.inverse.resize(source_index + 1, NO_ROW);
+        }
+        self.inverse[source_index] = self.rows.len();
+        self.rows.push(source_index);
+    }
+
+    /// The source index shown in the row.
+    pub fn source_index(&self, row: usize) -> Option<usize> {
+        self.rows.get(row).copied()
+    }
+
+    /// The row showing the source item, if it is shown.
+    pub fn row(&self, source_index: usize) -> Option<usize> {
+        self.inverse
+            .get(source_index)
+            .copied()
+            .filter(|row| *row != NO_ROW)
+    }
+
+    fn rebuild_inverse(&mut self) {
+        self.inverse.clear();
+        let source_len = self.rows.iter().max().map_or(0, |max| max + 1);
+        self.inverse.resize(source_len, NO_ROW);
+        for (row, source_index) in self.rows.iter().enumerate() {
+            self.inverse[*source_index] = row;
+        }
+    }
+}
+
+/// Maps rows to positions in a list of chunks, ex. the pages of a paged model or the groups of a grouped list,
+/// where each chunk shows some number of rows. Keeps a prefix sum of the rows shown by each chunk,
+/// so finding the chunk of a row is a binary search over chunks instead of a scan over rows.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChunkedRowMap {
+    // The first row of every chunk, followed by the total number of rows.
+    offsets: Vec<usize>,
+}
+
+impl Default for ChunkedRowMap {
+    fn default() -> Self {
+        Self { offsets: vec![0] }
+    }
+}
+
+impl ChunkedRowMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Build a map from the number of rows shown by every chunk.
+    pub fn from_chunk_lens(lens: impl IntoIterator<Item = usize>) -> Self {
+        let mut map = Self::new();
+        for len in lens {
+            map.push_chunk(len);
+        }
+        map
+    }
+
+    /// The total number of rows.
+    pub fn len(&self) -> usize {
+        self.offsets[self.offsets.len() - 1]
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn chunk_count(&self) -> usize {
+        self.offsets.len() - 1
+    }
+
+    pub fn clear(&mut self) {
+        self.offsets.truncate(1);
+    }
+
+    /// Add a chunk showing the given number of rows after the existing chunks.
+    pub fn push_chunk(&mut self, len: usize) {
+        self.offsets.push(self.len() + len);
+    }
+
+    /// Change the number of rows a chunk shows, ex. after a page loads. Shifts the rows of every later chunk.
+    pub fn set_chunk_len(&mut self, chunk: usize, len: usize) {
+        let Some(old_len) = self.chunk_len(chunk) else {
+            return;
+        };
+        for offset in &mut self.offsets[chunk + 1..] {
+            *offset = *offset - old_len + len;
+        }
+    }
+
+    /// The number of rows the chunk shows.
+    pub fn chunk_len(&self, chunk: usize) -> Option<usize> {
+        self.chunk_rows(chunk).map(|rows| rows.len())
+    }
+
+    /// The rows shown by the chunk.
+    pub fn chunk_rows(&self, chunk: usize) -> Option<Range<usize>> {
+        match self.offsets.get(chunk..chunk + 2)? {
+            [start, end] => Some(*start..*end),
+            _ => None,
+        }
+    }
+
+    /// The row showing an offset within a chunk.
+    pub fn row(&self, chunk: usize, offset: usize) -> Option<usize> {
+        let rows = self.chunk_rows(chunk)?;
+        (offset < rows.len()).then_some(rows.start + offset)
+    }
+
+    /// Convert a row into the chunk that shows it and the offset within that chunk.
+    pub fn locate(&self, row: usize) -> Option<(usize, usize)> {
+        if row >= self.len() {
+            return None;
+        }
+        // Empty chunks share their offset with the next chunk, so take the last chunk starting at or before the row.
+        let chunk = self.offsets.partition_point(|offset| *offset <= row) - 1;
+        Some((chunk, row - self.offsets[chunk]))
+    }
+
+    /// The chunks showing a range of rows, ex. the rows in view of a scroll area,
+    /// along with the range of offsets shown from each chunk. Skips chunks which show no rows.
+    pub fn visible_chunks(
+        &self,
+        rows: Range<usize>,
+    ) -> impl Iterator<Item = (usize, Range<usize>)> + '_ {
+        let rows = rows.start.min(self.len())..rows.end.min(self.len());
+        let first = self
+            .locate(rows.start)
+            .map_or(self.chunk_count(), |(chunk, _)| chunk);
+        (first..self.chunk_count())
+            .map(move |chunk| {
+                let start = self.offsets[chunk];
+                let end = self.offsets[chunk + 1];
+                (chunk, start, rows.start.max(start)..rows.end.min(end))
+            })
+            .take_while(move |(_, start, _)| *start < rows.end)
+            .filter(|(_, _, shown)| !shown.is_empty())
+            .map(|(chunk, start, shown)| (chunk, shown.start - start..shown.end - start))
+    }
+}
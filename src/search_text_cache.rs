@@ -0,0 +1,106 @@
+use std::cell::OnceCell;
+
+use crate::{ListModel, ModelEvent, ViewItem};
+
+struct SearchText {
+    text: String,
+    lowercase: String,
+}
+
+/// Caches the text of every item in a [ListModel] so filtering doesn't rebuild it every frame.
+/// Useful when [ViewItem::with_text] allocates, ex. text built from decoration dependencies.
+///
+/// Text is computed on first access. Keep the cache in sync by passing it the [ModelEvent]s
+/// recorded by a [crate::ObservableModel] and calling [SearchTextCache::update] before use.
+/// Rows past the end of the cache are treated as uncached.
+#[derive(Default)]
+pub struct SearchTextCache {
+    entries: Vec<OnceCell<SearchText>>,
+}
+
+impl SearchTextCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Match the cache's length to the model's. Rows added at the end are computed on first access.
+    pub fn update<M, I>(&mut self, model: &M)
+    where
+        M: ListModel<I>,
+    {
+        self.entries.resize_with(model.len(), OnceCell::new);
+    }
+
+    /// Shift, drop, or discard cached text to reflect a change to the model.
+    pub fn apply(&mut self, event: ModelEvent) {
+        match event {
+            ModelEvent::Inserted(index) => {
+                if index <= self.entries.len() {
+                    self.entries.insert(index, OnceCell::new());
+                }
+            }
+            ModelEvent::Removed(index) => {
+                if index < self.entries.len() {
+                    self.entries.remove(index);
+                }
+            }
+            ModelEvent::Moved { from, to } => {
+                if from < self.entries.len() && to < self.entries.len() {
+                    let entry = self.entries.remove(from);
+                    self.entries.insert(to, entry);
+                }
+            }
+            ModelEvent::Swapped(a, b) => {
+                if a < self.entries.len() && b < self.entries.len() {
+                    self.entries.swap(a, b);
+                }
+            }
+            ModelEvent::Changed(index) => {
+                if let Some(entry) = self.entries.get_mut(index) {
+                    entry.take();
+                }
+            }
+            ModelEvent::Reset => self.invalidate(),
+        }
+    }
+
+    /// Discard every cached string.
+    pub fn invalidate(&mut self) {
+        self.entries.clear();
+    }
+
+    /// The item's text as returned by [ViewItem::with_text].
+    pub fn text<'a, M, I>(&'a self, model: &M, index: usize) -> Option<&'a str>
+    where
+        M: ListModel<I>,
+        I: ViewItem,
+    {
+        self.entry(model, index).map(|entry| entry.text.as_str())
+    }
+
+    /// The item's text in lowercase, for case insensitive matching.
+    pub fn lowercase<'a, M, I>(&'a self, model: &M, index: usize) -> Option<&'a str>
+    where
+        M: ListModel<I>,
+        I: ViewItem,
+    {
+        self.entry(model, index)
+            .map(|entry| entry.lowercase.as_str())
+    }
+
+    fn entry<'a, M, I>(&'a self, model: &M, index: usize) -> Option<&'a SearchText>
+    where
+        M: ListModel<I>,
+        I: ViewItem,
+    {
+        let cell = self.entries.get(index)?;
+        if let Some(entry) = cell.get() {
+            return Some(entry);
+        }
+        let text = model.item(index)?.with_text(|text| text.to_string());
+        Some(cell.get_or_init(|| SearchText {
+            lowercase: text.to_lowercase(),
+            text,
+        }))
+    }
+}
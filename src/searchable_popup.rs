@@ -0,0 +1,190 @@
+use std::cell::Cell;
+use std::hash::Hash;
+
+use egui::{AboveOrBelow, Id, InnerResponse, PopupCloseBehavior, Stroke, TextEdit, Ui};
+
+/// A text field which opens a popup below (or above) itself when focused.
+/// The text typed into the field is persisted while the popup is open and handed to the popup contents as a search string.
+///
+/// This is the building block behind [crate::ModelDropDown]. Use it to build custom pickers with the same behavior.
+pub struct SearchablePopup {
+    id: Id,
+    popup_id: Id,
+    force_refresh: bool,
+    close_behavior: Option<PopupCloseBehavior>,
+    desired_width: Option<f32>,
+    just_opened: Cell<bool>,
+    label: Option<String>,
+    error: Option<String>,
+}
+
+impl SearchablePopup {
+    pub fn new(ui: &Ui, id_source: impl Hash) -> Self {
+        let id = ui.auto_id_with(id_source);
+        Self {
+            id,
+            popup_id: id.with("popup"),
+            force_refresh: false,
+            close_behavior: None,
+            desired_width: None,
+            just_opened: Cell::new(false),
+            label: None,
+            error: None,
+        }
+    }
+
+    /// Reset the field to the display text, discarding any search in progress.
+    pub fn force_refresh(mut self, force_refresh: bool) -> Self {
+        self.force_refresh = force_refresh;
+        self
+    }
+
+    /// Control when the popup closes.
+    /// By default, the popup closes when an item is selected or when the user clicks outside of it.
+    /// [PopupCloseBehavior::CloseOnClickOutside] and [PopupCloseBehavior::IgnoreClicks] leave the popup
+    /// open after a selection, which is useful for picking several items in a row.
+    pub fn close_behavior(mut self, close_behavior: Option<PopupCloseBehavior>) -> Self {
+        self.close_behavior = close_behavior;
+        self
+    }
+
+    /// Set the width of the search field, excluding margins. Defaults to [egui::style::Spacing::text_edit_width].
+    /// Use [f32::INFINITY] to fill the available width.
+    pub fn desired_width(mut self, desired_width: Option<f32>) -> Self {
+        self.desired_width = desired_width;
+        self
+    }
+
+    /// A label describing the field to screen readers.
+    pub fn label(mut self, label: Option<String>) -> Self {
+        self.label = label;
+        self
+    }
+
+    /// Draw the field with an error stroke and show the message when hovered.
+    pub fn error(mut self, error: Option<String>) -> Self {
+        self.error = error;
+        self
+    }
+
+    /// The ID used to persist the search text.
+    pub fn id(&self) -> Id {
+        self.id
+    }
+
+    pub fn is_open(&self, ui: &Ui) -> bool {
+        ui.memory(|mem| mem.is_popup_open(self.popup_id))
+    }
+
+    /// Whether the popup was opened during the current call to [SearchablePopup::show].
+    /// Useful for scrolling to the current selection.
+    pub fn just_opened(&self) -> bool {
+        self.just_opened.get()
+    }
+
+    /// Notify the popup that an item was selected. `text` replaces the search text if the popup closes.
+    pub fn select(&self, ui: &Ui, text: &str) {
+        if matches!(
+            self.close_behavior,
+            None | Some(PopupCloseBehavior::CloseOnClick)
+        ) {
+            ui.memory_mut(|mem| {
+                mem.data.insert_persisted(self.id, text.to_string());
+                mem.close_popup();
+            });
+        }
+    }
+
+    /// Show the search field and, if open, the popup.
+    /// `display_text` is shown in the field while the popup is closed.
+    /// The response is the text field's response. The inner value is [None] if the popup is closed.
+    pub fn show<R>(
+        &self,
+        ui: &mut Ui,
+        display_text: &str,
+        add_contents: impl FnOnce(&mut Ui, &str) -> R,
+    ) -> InnerResponse<Option<R>> {
+        let id = self.id;
+        let popup_id = self.popup_id;
+
+        let mut search = ui.memory_mut(|mem| {
+            if mem.is_popup_open(popup_id) {
+                mem.data
+                    .get_persisted_mut_or_default::<String>(id)
+                    .to_string()
+            } else {
+                display_text.to_string()
+            }
+        });
+
+        let background_color = ui.visuals().widgets.open.weak_bg_fill;
+        ui.visuals_mut().extreme_bg_color = background_color;
+        if self.error.is_some() {
+            let error_stroke = Stroke::new(1., ui.visuals().error_fg_color);
+            let widgets = &mut ui.visuals_mut().widgets;
+            widgets.inactive.bg_stroke = error_stroke;
+            widgets.hovered.bg_stroke = error_stroke;
+        }
+
+        let mut text_edit = TextEdit::singleline(&mut search);
+        if let Some(desired_width) = self.desired_width {
+            text_edit = text_edit.desired_width(desired_width);
+        }
+        let mut text_edit_response = ui.add(text_edit);
+        if let Some(error) = &self.error {
+            text_edit_response = text_edit_response.on_hover_text(error);
+        }
+        self.just_opened.set(text_edit_response.gained_focus());
+        if text_edit_response.gained_focus() {
+            search = Default::default();
+            ui.memory_mut(|mem| {
+                mem.data.insert_persisted(id, search.clone());
+                mem.open_popup(popup_id);
+            });
+        } else if self.force_refresh {
+            search = display_text.to_string();
+            ui.memory_mut(|mem| {
+                mem.data.insert_persisted(id, search.clone());
+            });
+        } else if text_edit_response.changed() {
+            ui.memory_mut(|mem| {
+                mem.data.insert_persisted(id, search.clone());
+            });
+        }
+
+        ui.reset_style();
+
+        #[cfg(feature = "accesskit")]
+        {
+            let expanded = self.is_open(ui);
+            ui.ctx()
+                .accesskit_node_builder(text_edit_response.id, |builder| {
+                    builder.set_role(egui::accesskit::Role::ComboBox);
+                    builder.set_expanded(expanded);
+                    if let Some(label) = &self.label {
+                        builder.set_name(label.as_str());
+                    }
+                });
+        }
+
+        // Copied from egui's ComboBox implementation.
+        let above_or_below = if ui.next_widget_position().y + ui.spacing().interact_size.y + 200.0
+            < ui.ctx().screen_rect().bottom()
+        {
+            AboveOrBelow::Below
+        } else {
+            AboveOrBelow::Above
+        };
+
+        let inner = egui::popup_above_or_below_widget(
+            ui,
+            popup_id,
+            &text_edit_response,
+            above_or_below,
+            self.close_behavior
+                .unwrap_or(PopupCloseBehavior::CloseOnClickOutside),
+            |ui| add_contents(ui, &search),
+        );
+        InnerResponse::new(inner, text_edit_response)
+    }
+}
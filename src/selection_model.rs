@@ -0,0 +1,135 @@
+use std::collections::BTreeSet;
+
+use egui::Modifiers;
+
+use crate::ModelEvent;
+
+/// Tracks which rows of a model are selected. Widgets bound to the same [SelectionModel] stay in sync.
+///
+/// * `current` is the focused row, ex. the row shown in a detail view.
+/// * `anchor` is where range selections (shift + click) start.
+/// * `selected` is every selected row, including `current`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SelectionModel {
+    current: Option<usize>,
+    anchor: Option<usize>,
+    selected: BTreeSet<usize>,
+}
+
+impl SelectionModel {
+    pub fn current(&self) -> Option<usize> {
+        self.current
+    }
+
+    pub fn anchor(&self) -> Option<usize> {
+        self.anchor
+    }
+
+    /// Every selected row in ascending order.
+    pub fn selected(&self) -> impl Iterator<Item = usize> + '_ {
+        self.selected.iter().copied()
+    }
+
+    pub fn is_selected(&self, index: usize) -> bool {
+        self.selected.contains(&index)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.selected.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.selected.len()
+    }
+
+    pub fn clear(&mut self) {
+        self.current = None;
+        self.anchor = None;
+        self.selected.clear();
+    }
+
+    /// Select a single row, or clear the selection if [None].
+    pub fn set_current(&mut self, index: Option<usize>) {
+        self.clear();
+        if let Some(index) = index {
+            self.select(index);
+        }
+    }
+
+    /// Select only the given row.
+    pub fn select(&mut self, index: usize) {
+        self.selected.clear();
+        self.selected.insert(index);
+        self.current = Some(index);
+        self.anchor = Some(index);
+    }
+
+    /// Add or remove a row from the selection without affecting other rows.
+    pub fn toggle(&mut self, index: usize) {
+        if !self.selected.remove(&index) {
+            self.selected.insert(index);
+        }
+        self.current = self.selected.contains(&index).then_some(index);
+        self.anchor = Some(index);
+    }
+
+    /// Select every row between the anchor and the given row.
+    pub fn extend_to(&mut self, index: usize) {
+        let anchor = self.anchor.unwrap_or(index);
+        self.selected = (anchor.min(index)..=anchor.max(index)).collect();
+        self.current = Some(index);
+        self.anchor = Some(anchor);
+    }
+
+    /// Select every row between the anchor and the given row in the order they're displayed,
+    /// leaving out rows which aren't displayed, ex. rows hidden by a filter or a collapsed group.
+    /// Selects only the given row if the anchor isn't displayed.
+    pub fn extend_to_visible(&mut self, index: usize, visible_rows: &[usize]) {
+        let anchor = self.anchor.unwrap_or(index);
+        let position = |row| visible_rows.iter().position(|r| *r == row);
+        let (Some(from), Some(to)) = (position(anchor), position(index)) else {
+            self.select(index);
+            return;
+        };
+        self.selected = visible_rows[from.min(to)..=from.max(to)]
+            .iter()
+            .copied()
+            .collect();
+        self.current = Some(index);
+        self.anchor = Some(anchor);
+    }
+
+    /// Like [SelectionModel::handle_click], but shift selects a range of the displayed rows.
+    pub fn handle_click_visible(
+        &mut self,
+        index: usize,
+        modifiers: Modifiers,
+        visible_rows: &[usize],
+    ) {
+        if modifiers.shift {
+            self.extend_to_visible(index, visible_rows);
+        } else {
+            self.handle_click(index, modifiers);
+        }
+    }
+
+    /// Update the selection for a click using the usual conventions:
+    /// ctrl (cmd on Mac) toggles the row and shift selects a range.
+    pub fn handle_click(&mut self, index: usize, modifiers: Modifiers) {
+        if modifiers.shift {
+            self.extend_to(index);
+        } else if modifiers.command {
+            self.toggle(index);
+        } else {
+            self.select(index);
+        }
+    }
+
+    /// Keep the selection pointing at the same items after a change to the model.
+    pub fn apply_event(&mut self, event: &ModelEvent) {
+        let map = |index: usize| event.map_index(index);
+        self.current = self.current.and_then(map);
+        self.anchor = self.anchor.and_then(map);
+        self.selected = self.selected.iter().filter_map(|i| map(*i)).collect();
+    }
+}
@@ -0,0 +1,240 @@
+use std::cell::{Ref, RefCell, RefMut};
+use std::rc::Rc;
+use std::sync::{Arc, Mutex, MutexGuard, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+use crate::{KeyedListModel, ListModel, ModelError};
+
+/// A model shared between several owners, ex. panels which display the same data.
+///
+/// [ListModel] returns references to items, so it can't be implemented for the shared
+/// container directly. Instead, borrow the model for the duration of a widget call:
+///
+/// ```ignore
+/// let model = Rc::new(RefCell::new(vec![]));
+/// model.with_model(|model| ui.add(EditableList::with_model(model, new_item, item_renderer)));
+/// ```
+///
+/// The guards returned by [RefCell::borrow_mut], [RwLock::write], and [Mutex::lock] also implement [ListModel].
+/// So do the guards returned by [RefCell::borrow] and [RwLock::read], which are read-only: their mutators do nothing
+/// and the `try_*` methods return [ModelError::Unsupported]. Use them to display a shared model without locking it for writing.
+pub trait SharedModel<M> {
+    /// Borrow the model mutably for the duration of the callback.
+    ///
+    /// # Panics
+    /// Panics if the model is already borrowed or the lock is poisoned.
+    fn with_model<R>(&self, consumer: impl FnOnce(&mut M) -> R) -> R;
+}
+
+impl<M> SharedModel<M> for Rc<RefCell<M>> {
+    fn with_model<R>(&self, consumer: impl FnOnce(&mut M) -> R) -> R {
+        consumer(&mut self.borrow_mut())
+    }
+}
+
+impl<M> SharedModel<M> for Arc<RwLock<M>> {
+    fn with_model<R>(&self, consumer: impl FnOnce(&mut M) -> R) -> R {
+        consumer(&mut self.write().expect("model lock was poisoned"))
+    }
+}
+
+impl<M> SharedModel<M> for Arc<Mutex<M>> {
+    fn with_model<R>(&self, consumer: impl FnOnce(&mut M) -> R) -> R {
+        consumer(&mut self.lock().expect("model lock was poisoned"))
+    }
+}
+
+macro_rules! forward_list_model {
+    ($($wrapper:ty),*) => {
+        $(
+            impl<M, I> ListModel<I> for $wrapper
+            where
+                M: ListModel<I>,
+            {
+                fn is_empty(&self) -> bool {
+                    (**self).is_empty()
+                }
+
+                fn len(&self) -> usize {
+                    (**self).len()
+                }
+
+                fn item(&self, index: usize) -> Option<&I> {
+                    (**self).item(index)
+                }
+
+                fn item_mut(&mut self, index: usize) -> Option<&mut I> {
+                    (**self).item_mut(index)
+                }
+
+                fn add(&mut self, item: I) {
+                    (**self).add(item)
+                }
+
+                fn clear(&mut self) {
+                    (**self).clear()
+                }
+
+                fn extend_items(&mut self, items: impl IntoIterator<Item = I>) {
+                    (**self).extend_items(items)
+                }
+
+                fn insert(&mut self, index: usize, item: I) {
+                    (**self).insert(index, item)
+                }
+
+                fn remove(&mut self, index: usize) {
+                    (**self).remove(index)
+                }
+
+                fn swap_items(&mut self, a: usize, b: usize) {
+                    (**self).swap_items(a, b)
+                }
+
+                fn copy(&mut self, a: usize, b: usize) {
+                    (**self).copy(a, b)
+                }
+
+                fn move_item(&mut self, from: usize, to: usize) {
+                    (**self).move_item(from, to)
+                }
+
+                fn remove_many(&mut self, indices: &[usize]) {
+                    (**self).remove_many(indices)
+                }
+
+                fn row_to_index(&self, row_number: usize) -> Option<usize> {
+                    (**self).row_to_index(row_number)
+                }
+
+                fn generation(&self) -> Option<u64> {
+                    (**self).generation()
+                }
+
+                fn try_add(&mut self, item: I) -> Result<(), ModelError> {
+                    (**self).try_add(item)
+                }
+
+                fn try_insert(&mut self, index: usize, item: I) -> Result<(), ModelError> {
+                    (**self).try_insert(index, item)
+                }
+
+                fn try_remove(&mut self, index: usize) -> Result<(), ModelError> {
+                    (**self).try_remove(index)
+                }
+
+                fn try_swap_items(&mut self, a: usize, b: usize) -> Result<(), ModelError> {
+                    (**self).try_swap_items(a, b)
+                }
+
+                fn try_move_item(&mut self, from: usize, to: usize) -> Result<(), ModelError> {
+                    (**self).try_move_item(from, to)
+                }
+
+                fn try_copy(&mut self, a: usize, b: usize) -> Result<(), ModelError> {
+                    (**self).try_copy(a, b)
+                }
+            }
+
+            impl<M, I> KeyedListModel<I> for $wrapper
+            where
+                M: KeyedListModel<I>,
+            {
+                fn index_of(&self, key: &str) -> Option<usize> {
+                    (**self).index_of(key)
+                }
+            }
+        )*
+    };
+}
+
+forward_list_model!(
+    &mut M,
+    Box<M>,
+    RefMut<'_, M>,
+    RwLockWriteGuard<'_, M>,
+    MutexGuard<'_, M>
+);
+
+macro_rules! read_only_list_model {
+    ($($wrapper:ty),*) => {
+        $(
+            impl<M, I> ListModel<I> for $wrapper
+            where
+                M: ListModel<I>,
+            {
+                fn is_empty(&self) -> bool {
+                    (**self).is_empty()
+                }
+
+                fn len(&self) -> usize {
+                    (**self).len()
+                }
+
+                fn item(&self, index: usize) -> Option<&I> {
+                    (**self).item(index)
+                }
+
+                fn item_mut(&mut self, _: usize) -> Option<&mut I> {
+                    None
+                }
+
+                fn add(&mut self, _: I) {}
+
+                fn insert(&mut self, _: usize, _: I) {}
+
+                fn remove(&mut self, _: usize) {}
+
+                fn swap_items(&mut self, _: usize, _: usize) {}
+
+                fn copy(&mut self, _: usize, _: usize) {}
+
+                fn row_to_index(&self, row_number: usize) -> Option<usize> {
+                    (**self).row_to_index(row_number)
+                }
+
+                fn generation(&self) -> Option<u64> {
+                    (**self).generation()
+                }
+
+                fn try_add(&mut self, _: I) -> Result<(), ModelError> {
+                    Err(READ_ONLY)
+                }
+
+                fn try_insert(&mut self, _: usize, _: I) -> Result<(), ModelError> {
+                    Err(READ_ONLY)
+                }
+
+                fn try_remove(&mut self, _: usize) -> Result<(), ModelError> {
+                    Err(READ_ONLY)
+                }
+
+                fn try_swap_items(&mut self, _: usize, _: usize) -> Result<(), ModelError> {
+                    Err(READ_ONLY)
+                }
+
+                fn try_move_item(&mut self, _: usize, _: usize) -> Result<(), ModelError> {
+                    Err(READ_ONLY)
+                }
+
+                fn try_copy(&mut self, _: usize, _: usize) -> Result<(), ModelError> {
+                    Err(READ_ONLY)
+                }
+            }
+
+            impl<M, I> KeyedListModel<I> for $wrapper
+            where
+                M: KeyedListModel<I>,
+            {
+                fn index_of(&self, key: &str) -> Option<usize> {
+                    (**self).index_of(key)
+                }
+            }
+        )*
+    };
+}
+
+read_only_list_model!(Ref<'_, M>, RwLockReadGuard<'_, M>);
+
+const READ_ONLY: ModelError = ModelError::Unsupported(
+    "modifying a model through a read guard, borrow it mutably with SharedModel::with_model instead",
+);
@@ -0,0 +1,265 @@
+use std::cmp::Ordering;
+
+use crate::{InvalidationHandle, ListModel, RowMap, TextCollator, ViewItem};
+
+type ItemComparator<'a, I> = Box<dyn Fn(&I, &I) -> Ordering + 'a>;
+
+/// A utility for showing sorted data on a UI by generating a "proxy model".
+/// The source model is never reordered.
+///
+/// Enable the `serde` feature to persist the sort direction.
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct SortProxyBuilder {
+    descending: bool,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    requires_refresh: bool,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    proxy_indices: RowMap,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    revision: u64,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    source: InvalidationHandle,
+}
+
+impl Default for SortProxyBuilder {
+    fn default() -> Self {
+        Self {
+            descending: false,
+            requires_refresh: true,
+            proxy_indices: RowMap::new(),
+            revision: 0,
+            source: InvalidationHandle::default(),
+        }
+    }
+}
+
+impl SortProxyBuilder {
+    /// Retrieve the index of an item in the source model from its index in the proxy model.
+    pub fn source_index<M, I>(&self, index: usize, source_model: &M) -> Option<usize>
+    where
+        M: ListModel<I>,
+    {
+        // The client might call this without refreshing after a source model change (but they shouldn't).
+        // Compare against the source model's length to be safe.
+        self.proxy_indices
+            .source_index(index)
+            .filter(|real_index| *real_index < source_model.len())
+    }
+
+    /// Retrieve the proxy index of an item from its index in the source model.
+    pub fn proxy_index(&self, source_index: usize) -> Option<usize> {
+        self.proxy_indices.row(source_index)
+    }
+
+    pub fn descending(&self) -> bool {
+        self.descending
+    }
+
+    /// Reverse the sort order.
+    pub fn set_descending(&mut self, descending: bool) {
+        if self.descending != descending {
+            self.descending = descending;
+            self.requires_refresh = true;
+        }
+    }
+
+    /// Request that the sort proxy refresh its indices.
+    pub fn request_refresh(&mut self) {
+        self.requires_refresh = true;
+    }
+
+    /// Build a view of a source model sorted by display text using the given collator.
+    /// Use [crate::OrdinalCollator] for alphabetical order or [crate::NaturalCollator] for natural order.
+    /// See [SortProxyBuilder::model_by] for when the proxy refreshes.
+    pub fn model<'a, M, I>(
+        &'a mut self,
+        requires_refresh: bool,
+        model: &'a mut M,
+        collator: &'a dyn TextCollator,
+    ) -> SortProxyModel<'a, M, I>
+    where
+        M: ListModel<I>,
+        I: ViewItem,
+    {
+        self.model_by(requires_refresh, model, move |a: &I, b: &I| {
+            a.with_text(|a| b.with_text(|b| collator.compare(a, b)))
+        })
+    }
+
+    /// Build a view of a source model sorted by [ViewItem::sort_key].
+    /// The collator compares text keys. See [SortProxyBuilder::model_by] for when the proxy refreshes.
+    pub fn model_by_sort_key<'a, M, I>(
+        &'a mut self,
+        requires_refresh: bool,
+        model: &'a mut M,
+        collator: &'a dyn TextCollator,
+    ) -> SortProxyModel<'a, M, I>
+    where
+        M: ListModel<I>,
+        I: ViewItem,
+    {
+        self.model_by(requires_refresh, model, move |a: &I, b: &I| {
+            a.sort_key().compare(&b.sort_key(), collator)
+        })
+    }
+
+    /// Build a view of a source model sorted with a custom comparator.
+    /// Will ONLY trigger a refresh when required. There are three cases for this:
+    /// * First time building a proxy (detected automatically)
+    /// * Sort order was changed (detected automatically)
+    /// * Source model was changed outside of the proxy. YOU must tell the proxy when this happened,
+    ///   unless the source reports a [ListModel::generation] (ex. [crate::ObservableModel]).
+    pub fn model_by<'a, M, I>(
+        &'a mut self,
+        requires_refresh: bool,
+        model: &'a mut M,
+        compare: impl Fn(&I, &I) -> Ordering + 'a,
+    ) -> SortProxyModel<'a, M, I>
+    where
+        M: ListModel<I>,
+    {
+        let descending = self.descending;
+        let source_changed = self.source.update(model);
+        let refresh = self.requires_refresh || requires_refresh || source_changed;
+        self.requires_refresh = false;
+        let mut proxy = SortProxyModel {
+            proxy_indices: &mut self.proxy_indices,
+            revision: &mut self.revision,
+            requires_refresh: &mut self.requires_refresh,
+            model,
+            compare: Box::new(move |a: &I, b: &I| {
+                if descending {
+                    compare(b, a)
+                } else {
+                    compare(a, b)
+                }
+            }),
+        };
+        if refresh {
+            proxy.sort();
+        }
+        proxy
+    }
+}
+
+/// A sorted view of a [ListModel] generated by a [SortProxyBuilder].
+/// Mutations are forwarded to the source model, after which the view is sorted again.
+///
+/// Sorting advances [ListModel::generation], so a proxy layered on top of this one (ex. a
+/// [crate::FilterProxyModel]) rebuilds when the order changes.
+pub struct SortProxyModel<'a, M, I> {
+    proxy_indices: &'a mut RowMap,
+    revision: &'a mut u64,
+    requires_refresh: &'a mut bool,
+    model: &'a mut M,
+    compare: ItemComparator<'a, I>,
+}
+
+impl<M, I> SortProxyModel<'_, M, I>
+where
+    M: ListModel<I>,
+{
+    fn sort(&mut self) {
+        let model = &self.model;
+        let compare = &self.compare;
+        let mut rows: Vec<usize> = (0..model.len()).collect();
+        // Stable sort so equal items keep their source order.
+        rows.sort_by(|a, b| match (model.item(*a), model.item(*b)) {
+            (Some(a), Some(b)) => compare(a, b),
+            _ => Ordering::Equal,
+        });
+        *self.proxy_indices = RowMap::from_rows(rows);
+        *self.revision = self.revision.wrapping_add(1);
+    }
+
+    /// Retrieve the index of an item in the source model from its index in this proxy.
+    pub fn source_index(&self, index: usize) -> Option<usize> {
+        self.proxy_indices
+            .source_index(index)
+            .filter(|index| *index < self.model.len())
+    }
+
+    /// Retrieve the proxy index of an item from its index in the source model.
+    pub fn proxy_index(&self, source_index: usize) -> Option<usize> {
+        self.proxy_indices.row(source_index)
+    }
+}
+
+impl<M, I> ListModel<I> for SortProxyModel<'_, M, I>
+where
+    M: ListModel<I>,
+{
+    fn is_empty(&self) -> bool {
+        self.proxy_indices.is_empty()
+    }
+
+    fn len(&self) -> usize {
+        self.proxy_indices.len()
+    }
+
+    fn item(&self, index: usize) -> Option<&I> {
+        self.source_index(index)
+            .and_then(|source_index| self.model.item(source_index))
+    }
+
+    /// Editing an item may change its position, so the proxy is sorted again the next time it is built.
+    fn item_mut(&mut self, index: usize) -> Option<&mut I> {
+        *self.requires_refresh = true;
+        self.source_index(index)
+            .and_then(|source_index| self.model.item_mut(source_index))
+    }
+
+    fn add(&mut self, item: I) {
+        self.model.add(item);
+        self.sort();
+    }
+
+    /// Inserts the item in the source model before the item at the given proxy index.
+    /// The item is then moved to its sorted position in the proxy.
+    fn insert(&mut self, index: usize, item: I) {
+        let source_index = if index == self.len() {
+            Some(self.model.len())
+        } else {
+            self.source_index(index)
+        };
+        if let Some(source_index) = source_index {
+            self.model.insert(source_index, item);
+            self.sort();
+        }
+    }
+
+    fn remove(&mut self, index: usize) {
+        if let Some(source_index) = self.source_index(index) {
+            self.model.remove(source_index);
+            self.sort();
+        }
+    }
+
+    fn swap_items(&mut self, a: usize, b: usize) {
+        if let (Some(a), Some(b)) = (self.source_index(a), self.source_index(b)) {
+            self.model.swap_items(a, b);
+            self.sort();
+        }
+    }
+
+    fn copy(&mut self, a: usize, b: usize) {
+        if let (Some(a), Some(b)) = (self.source_index(a), self.source_index(b)) {
+            self.model.copy(a, b);
+            self.sort();
+        }
+    }
+
+    fn row_to_index(&self, row_number: usize) -> Option<usize> {
+        self.proxy_indices
+            .source_index(row_number)
+            .and_then(|index| self.model.row_to_index(index))
+    }
+
+    fn generation(&self) -> Option<u64> {
+        Some(*self.revision)
+    }
+}
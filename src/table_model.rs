@@ -0,0 +1,321 @@
+use std::borrow::Cow;
+use std::marker::PhantomData;
+
+use egui::Image;
+use indexmap::IndexMap;
+
+use crate::{DecorationKind, ItemStyle, ListModel, ViewItem};
+
+/// The type of data displayed in a table column. Widgets may use this to align, format, or sort cells.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColumnKind {
+    #[default]
+    Text,
+    Integer,
+    Float,
+    Bool,
+}
+
+/// Grid-like data that can be rendered by multi-column widgets.
+/// Cells are addressed by `(row, column)`.
+pub trait TableModel {
+    type DecorationDependencies;
+
+    /// The number of rows in this model.
+    fn row_count(&self) -> usize;
+
+    /// The number of columns in this model.
+    fn column_count(&self) -> usize;
+
+    /// The header text of the given column.
+    fn column_header(&self, column: usize) -> Cow<'_, str>;
+
+    /// The type of data in the given column.
+    #[allow(unused)]
+    fn column_kind(&self, column: usize) -> ColumnKind {
+        ColumnKind::Text
+    }
+
+    /// Indicates whether ANY cell in the given column could be decorated.
+    #[allow(unused)]
+    fn column_decorated(&self, column: usize, kind: DecorationKind<'_>) -> bool {
+        false
+    }
+
+    /// The numeric value of a cell, used for aggregates. By default, the text of
+    /// [ColumnKind::Integer] and [ColumnKind::Float] cells is parsed.
+    fn cell_value(&self, row: usize, column: usize) -> Option<f64> {
+        match self.column_kind(column) {
+            ColumnKind::Integer | ColumnKind::Float => {
+                self.with_cell_text(row, column, |text| text.trim().parse().ok())
+            }
+            ColumnKind::Text | ColumnKind::Bool => None,
+        }
+    }
+
+    /// The group a row belongs to when a table is grouped with [RowGrouping::ModelKey].
+    /// Rows without a group key are placed in an unnamed group.
+    #[allow(unused)]
+    fn group_key(&self, row: usize) -> Option<Cow<'_, str>> {
+        None
+    }
+
+    /// How the text of a cell should be rendered.
+    #[allow(unused)]
+    fn cell_style(&self, row: usize, column: usize) -> ItemStyle {
+        ItemStyle::default()
+    }
+
+    /// Retrieve the display text of a cell. Out of bounds cells are treated as empty.
+    fn with_cell_text<F, R>(&self, row: usize, column: usize, consumer: F) -> R
+    where
+        F: FnOnce(&str) -> R;
+
+    /// Retrieve a description of a cell which widgets show on hover.
+    #[allow(unused)]
+    fn with_cell_tooltip<F, R>(&self, row: usize, column: usize, consumer: F) -> R
+    where
+        F: FnOnce(Option<&str>) -> R,
+    {
+        consumer(None)
+    }
+
+    /// Retrieve the decoration of a cell.
+    #[allow(unused)]
+    fn with_cell_decoration<F, R>(
+        &self,
+        dependencies: &Self::DecorationDependencies,
+        row: usize,
+        column: usize,
+        kind: DecorationKind<'_>,
+        consumer: F,
+    ) -> R
+    where
+        F: FnOnce(Option<Image>) -> R,
+    {
+        consumer(None)
+    }
+}
+
+/// A [TableModel] whose cells can be edited as text.
+pub trait EditableTableModel: TableModel {
+    /// Parse the text and store it in the cell. Returns false if the text was rejected.
+    fn set_cell_text(&mut self, row: usize, column: usize, text: &str) -> bool;
+
+    /// A stable identifier for the row, used in [CellEdited] events. Defaults to the row number.
+    fn row_key(&self, row: usize) -> Cow<'_, str> {
+        Cow::Owned(row.to_string())
+    }
+}
+
+/// Emitted when the user edits a cell in a table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CellEdited {
+    /// The [EditableTableModel::row_key] of the edited row.
+    pub row_key: String,
+    pub column: usize,
+    /// The text of the cell before the edit.
+    pub old: String,
+    /// The text of the cell after the edit.
+    pub new: String,
+}
+
+/// How a table groups its rows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RowGrouping {
+    /// Group rows with the same text in the given column.
+    Column(usize),
+    /// Group rows by [TableModel::group_key].
+    ModelKey,
+}
+
+impl RowGrouping {
+    /// Split the rows of the model into groups. Groups are ordered by their first row.
+    pub fn groups<T>(&self, model: &T) -> Vec<(String, Vec<usize>)>
+    where
+        T: TableModel,
+    {
+        let mut groups: IndexMap<String, Vec<usize>> = IndexMap::new();
+        for row in 0..model.row_count() {
+            let key = match self {
+                RowGrouping::Column(column) => model.with_cell_text(row, *column, str::to_string),
+                RowGrouping::ModelKey => model
+                    .group_key(row)
+                    .map(Cow::into_owned)
+                    .unwrap_or_default(),
+            };
+            groups.entry(key).or_default().push(row);
+        }
+        groups.into_iter().collect()
+    }
+}
+
+/// A summary of the values in a table column. Cells without a value are skipped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Aggregate {
+    Sum,
+    Mean,
+    Min,
+    Max,
+    Count,
+}
+
+impl Aggregate {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Aggregate::Sum => "Sum",
+            Aggregate::Mean => "Mean",
+            Aggregate::Min => "Min",
+            Aggregate::Max => "Max",
+            Aggregate::Count => "Count",
+        }
+    }
+
+    /// Compute the aggregate over a column. Returns [None] if no cell has a value.
+    pub fn compute<T>(&self, model: &T, column: usize) -> Option<f64>
+    where
+        T: TableModel,
+    {
+        self.compute_rows(model, column, 0..model.row_count())
+    }
+
+    /// Compute the aggregate over the given rows of a column.
+    pub fn compute_rows<T>(
+        &self,
+        model: &T,
+        column: usize,
+        rows: impl IntoIterator<Item = usize>,
+    ) -> Option<f64>
+    where
+        T: TableModel,
+    {
+        let values = rows
+            .into_iter()
+            .filter_map(|row| model.cell_value(row, column));
+        match self {
+            Aggregate::Sum => values.reduce(|a, b| a + b),
+            Aggregate::Mean => {
+                let (sum, count) = values.fold((0., 0), |(sum, count), v| (sum + v, count + 1));
+                (count > 0).then(|| sum / count as f64)
+            }
+            Aggregate::Min => values.reduce(f64::min),
+            Aggregate::Max => values.reduce(f64::max),
+            Aggregate::Count => Some(values.count() as f64),
+        }
+    }
+
+    /// Compute the aggregate and format it for display, ex. "Sum: 120".
+    pub fn display<T>(&self, model: &T, column: usize) -> String
+    where
+        T: TableModel,
+    {
+        self.display_rows(model, column, 0..model.row_count())
+    }
+
+    /// Compute the aggregate over the given rows and format it for display.
+    pub fn display_rows<T>(
+        &self,
+        model: &T,
+        column: usize,
+        rows: impl IntoIterator<Item = usize>,
+    ) -> String
+    where
+        T: TableModel,
+    {
+        match (self, self.compute_rows(model, column, rows)) {
+            (Aggregate::Mean, Some(value)) => format!("{}: {:.2}", self.label(), value),
+            (_, Some(value)) => format!("{}: {}", self.label(), value),
+            (_, None) => format!("{}: -", self.label()),
+        }
+    }
+}
+
+/// Presents a [ListModel] as a table with a single column.
+pub struct SingleColumnTable<'a, M, I> {
+    header: &'a str,
+    model: &'a M,
+    _item: PhantomData<I>,
+}
+
+impl<'a, M, I> SingleColumnTable<'a, M, I>
+where
+    M: ListModel<I>,
+    I: ViewItem,
+{
+    pub fn new(header: &'a str, model: &'a M) -> Self {
+        Self {
+            header,
+            model,
+            _item: PhantomData,
+        }
+    }
+}
+
+impl<M, I> TableModel for SingleColumnTable<'_, M, I>
+where
+    M: ListModel<I>,
+    I: ViewItem,
+{
+    type DecorationDependencies = I::DecorationDependencies;
+
+    fn row_count(&self) -> usize {
+        self.model.len()
+    }
+
+    fn column_count(&self) -> usize {
+        1
+    }
+
+    fn column_header(&self, _: usize) -> Cow<'_, str> {
+        Cow::Borrowed(self.header)
+    }
+
+    fn column_decorated(&self, column: usize, kind: DecorationKind<'_>) -> bool {
+        column == 0 && I::decorated(kind)
+    }
+
+    fn cell_style(&self, row: usize, column: usize) -> ItemStyle {
+        self.model
+            .item(row)
+            .filter(|_| column == 0)
+            .map(|item| item.style_hint())
+            .unwrap_or_default()
+    }
+
+    fn with_cell_text<F, R>(&self, row: usize, column: usize, consumer: F) -> R
+    where
+        F: FnOnce(&str) -> R,
+    {
+        match self.model.item(row).filter(|_| column == 0) {
+            Some(item) => item.with_text(consumer),
+            None => consumer(""),
+        }
+    }
+
+    fn with_cell_tooltip<F, R>(&self, row: usize, column: usize, consumer: F) -> R
+    where
+        F: FnOnce(Option<&str>) -> R,
+    {
+        match self.model.item(row).filter(|_| column == 0) {
+            Some(item) => item.with_tooltip(consumer),
+            None => consumer(None),
+        }
+    }
+
+    fn with_cell_decoration<F, R>(
+        &self,
+        dependencies: &Self::DecorationDependencies,
+        row: usize,
+        column: usize,
+        kind: DecorationKind<'_>,
+        consumer: F,
+    ) -> R
+    where
+        F: FnOnce(Option<Image>) -> R,
+    {
+        match self.model.item(row).filter(|_| column == 0) {
+            Some(item) => item.with_decoration(dependencies, kind, consumer),
+            None => consumer(None),
+        }
+    }
+}
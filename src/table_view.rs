@@ -0,0 +1,665 @@
+use std::collections::BTreeSet;
+use std::hash::Hash;
+
+use egui::{
+    vec2, Align, Context, Id, Key, Label, Layout, Rect, Response, RichText, ScrollArea, Sense,
+    TextEdit, Ui,
+};
+
+use crate::{
+    emit_event, Aggregate, CellEdited, ColumnKind, DecorationKind, EditableTableModel, RowGrouping,
+    TableModel,
+};
+
+/// The order and visibility of a table's columns.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ColumnLayout {
+    order: Vec<usize>,
+    hidden: BTreeSet<usize>,
+}
+
+impl ColumnLayout {
+    /// Load the layout for the table with the given ID. The layout is persisted with egui's memory.
+    pub fn load(ctx: &Context, id: Id) -> Option<Self> {
+        ctx.data_mut(|data| data.get_persisted::<String>(id.with("columns")))
+            .and_then(|encoded| Self::decode(&encoded))
+    }
+
+    /// Save the layout for the table with the given ID.
+    pub fn store(&self, ctx: &Context, id: Id) {
+        ctx.data_mut(|data| data.insert_persisted(id.with("columns"), self.encode()));
+    }
+
+    // Stored as a string so the layout persists without requiring serde.
+    fn encode(&self) -> String {
+        let join = |columns: &mut dyn Iterator<Item = &usize>| {
+            columns
+                .map(|column| column.to_string())
+                .collect::<Vec<_>>()
+                .join(",")
+        };
+        format!(
+            "{};{}",
+            join(&mut self.order.iter()),
+            join(&mut self.hidden.iter())
+        )
+    }
+
+    fn decode(encoded: &str) -> Option<Self> {
+        let (order, hidden) = encoded.split_once(';')?;
+        let parse = |list: &str| -> Option<Vec<usize>> {
+            list.split(',')
+                .filter(|column| !column.is_empty())
+                .map(|column| column.parse().ok())
+                .collect()
+        };
+        Some(Self {
+            order: parse(order)?,
+            hidden: parse(hidden)?.into_iter().collect(),
+        })
+    }
+
+    /// Make sure every column in the model appears exactly once.
+    fn normalize(&mut self, column_count: usize) {
+        let mut seen = BTreeSet::new();
+        self.order
+            .retain(|column| *column < column_count && seen.insert(*column));
+        self.order
+            .extend((0..column_count).filter(|column| !seen.contains(column)));
+        self.hidden.retain(|column| *column < column_count);
+        if self.visible_columns().next().is_none() {
+            self.hidden.clear();
+        }
+    }
+
+    /// The visible columns in display order.
+    pub fn visible_columns(&self) -> impl Iterator<Item = usize> + '_ {
+        self.order
+            .iter()
+            .copied()
+            .filter(|column| !self.hidden.contains(column))
+    }
+
+    /// Every column in display order, including hidden columns.
+    pub fn columns(&self) -> &[usize] {
+        &self.order
+    }
+
+    pub fn is_visible(&self, column: usize) -> bool {
+        !self.hidden.contains(&column)
+    }
+
+    pub fn set_visible(&mut self, column: usize, visible: bool) {
+        if visible {
+            self.hidden.remove(&column);
+        } else {
+            self.hidden.insert(column);
+        }
+    }
+
+    /// Move a column to a new display position.
+    pub fn move_column(&mut self, column: usize, position: usize) {
+        if let Some(current) = self.order.iter().position(|c| *c == column) {
+            self.order.remove(current);
+            let position = position.min(self.order.len());
+            self.order.insert(position, column);
+        }
+    }
+
+    fn position(&self, column: usize) -> Option<usize> {
+        self.order.iter().position(|c| *c == column)
+    }
+}
+
+/// A widget for displaying a [TableModel].
+pub struct TableView<'a> {
+    id: Id,
+    column_chooser: bool,
+    default_hidden: &'a [usize],
+    pinned_header: bool,
+    aggregates: Vec<(usize, Aggregate)>,
+    grouping: Option<RowGrouping>,
+    group_aggregates: bool,
+    last_visible_row: Option<&'a mut Option<usize>>,
+}
+
+impl<'a> TableView<'a> {
+    pub fn new(id_source: impl Hash) -> Self {
+        Self {
+            id: Id::new(id_source).with("table_view"),
+            column_chooser: true,
+            default_hidden: &[],
+            pinned_header: true,
+            aggregates: vec![],
+            grouping: None,
+            group_aggregates: false,
+            last_visible_row: None,
+        }
+    }
+
+    /// Let users hide, show, and reorder columns by right clicking the header. Enabled by default.
+    pub fn column_chooser(mut self, column_chooser: bool) -> Self {
+        self.column_chooser = column_chooser;
+        self
+    }
+
+    /// Columns which are hidden until the user chooses to show them.
+    pub fn default_hidden(mut self, default_hidden: &'a [usize]) -> Self {
+        self.default_hidden = default_hidden;
+        self
+    }
+
+    /// Keep the header visible while scrolling through rows. Enabled by default.
+    pub fn pinned_header(mut self, pinned_header: bool) -> Self {
+        self.pinned_header = pinned_header;
+        self
+    }
+
+    /// Show an aggregate for the given column in a footer row pinned to the bottom of the table.
+    /// A column may have multiple aggregates.
+    pub fn aggregate(mut self, column: usize, aggregate: Aggregate) -> Self {
+        self.aggregates.push((column, aggregate));
+        self
+    }
+
+    /// Group rows under collapsible headers which show the number of rows in each group.
+    pub fn group_by(mut self, grouping: RowGrouping) -> Self {
+        self.grouping = Some(grouping);
+        self
+    }
+
+    /// Show the table's aggregates for each group in its header row.
+    pub fn group_aggregates(mut self, group_aggregates: bool) -> Self {
+        self.group_aggregates = group_aggregates;
+        self
+    }
+
+    /// Set to the last row on screen after the table is shown, or [None] if no row is.
+    /// Use it to load more rows as the table scrolls, ex. with [crate::InfiniteScroll::load_near].
+    pub fn last_visible_row(mut self, last_visible_row: &'a mut Option<usize>) -> Self {
+        self.last_visible_row = Some(last_visible_row);
+        self
+    }
+
+    /// The ID used to persist the table's [ColumnLayout].
+    pub fn id(&self) -> Id {
+        self.id
+    }
+
+    fn column_chooser_ui<T>(&self, ui: &mut Ui, model: &T, layout: &mut ColumnLayout, column: usize)
+    where
+        T: TableModel,
+    {
+        let position = layout.position(column).unwrap_or_default();
+        ui.horizontal(|ui| {
+            if ui
+                .add_enabled(position > 0, egui::Button::new("Move left"))
+                .clicked()
+            {
+                layout.move_column(column, position - 1);
+                ui.close_menu();
+            }
+            if ui
+                .add_enabled(
+                    position + 1 < layout.columns().len(),
+                    egui::Button::new("Move right"),
+                )
+                .clicked()
+            {
+                layout.move_column(column, position + 1);
+                ui.close_menu();
+            }
+        });
+        ui.separator();
+        let visible_count = layout.visible_columns().count();
+        for column in layout.columns().to_vec() {
+            let mut visible = layout.is_visible(column);
+            // Don't allow hiding the last visible column.
+            let enabled = !visible || visible_count > 1;
+            if ui
+                .add_enabled(
+                    enabled,
+                    egui::Checkbox::new(&mut visible, model.column_header(column).as_ref()),
+                )
+                .changed()
+            {
+                layout.set_visible(column, visible);
+            }
+        }
+        ui.separator();
+        if ui.button("Reset columns").clicked() {
+            *layout = self.default_layout(model.column_count());
+            ui.close_menu();
+        }
+    }
+
+    fn default_layout(&self, column_count: usize) -> ColumnLayout {
+        let mut layout = ColumnLayout {
+            order: vec![],
+            hidden: self.default_hidden.iter().copied().collect(),
+        };
+        layout.normalize(column_count);
+        layout
+    }
+
+    pub fn show<T>(
+        self,
+        ui: &mut Ui,
+        model: &T,
+        dependencies: &T::DecorationDependencies,
+    ) -> Response
+    where
+        T: TableModel,
+    {
+        let (response, last_visible) =
+            self.show_impl(ui, model, dependencies, &mut CellEditor::default());
+        if let Some(last_visible_row) = self.last_visible_row {
+            *last_visible_row = last_visible;
+        }
+        response
+    }
+
+    /// Show the table and let users edit cells by double clicking them.
+    /// Each change is emitted as a [CellEdited] event.
+    pub fn show_editable<T>(
+        self,
+        ui: &mut Ui,
+        model: &mut T,
+        dependencies: &T::DecorationDependencies,
+    ) -> Response
+    where
+        T: EditableTableModel,
+    {
+        let editor_id = self.id.with("editor");
+        let mut editor = CellEditor {
+            enabled: true,
+            editing: ui.data(|data| data.get_temp(editor_id)),
+            commit: None,
+        };
+        let (response, last_visible) = self.show_impl(ui, model, dependencies, &mut editor);
+        if let Some(last_visible_row) = self.last_visible_row {
+            *last_visible_row = last_visible;
+        }
+        ui.data_mut(|data| match editor.editing {
+            Some(editing) => data.insert_temp(editor_id, editing),
+            None => data.remove::<(usize, usize, String)>(editor_id),
+        });
+
+        if let Some((row, column, new)) = editor.commit {
+            let old = model.with_cell_text(row, column, str::to_string);
+            if old != new && model.set_cell_text(row, column, &new) {
+                // Report the stored text in case the model normalized it.
+                let new = model.with_cell_text(row, column, str::to_string);
+                emit_event(
+                    ui.ctx(),
+                    CellEdited {
+                        row_key: model.row_key(row).into_owned(),
+                        column,
+                        old,
+                        new,
+                    },
+                );
+            }
+        }
+        response
+    }
+
+    fn show_impl<T>(
+        &self,
+        ui: &mut Ui,
+        model: &T,
+        dependencies: &T::DecorationDependencies,
+        editor: &mut CellEditor,
+    ) -> (Response, Option<usize>)
+    where
+        T: TableModel,
+    {
+        let column_count = model.column_count();
+        let mut layout = ColumnLayout::load(ui.ctx(), self.id)
+            .unwrap_or_else(|| self.default_layout(column_count));
+        layout.normalize(column_count);
+
+        // Column widths grow to fit the widest cell seen so far.
+        let widths_id = self.id.with("widths");
+        let mut widths: Vec<f32> = ui.data(|data| data.get_temp(widths_id)).unwrap_or_default();
+        widths.resize(column_count, 0.);
+
+        let row_height = ui.spacing().interact_size.y;
+        let footer_height = if self.aggregates.is_empty() {
+            0.
+        } else {
+            row_height + ui.spacing().item_spacing.y * 2. + 6.
+        };
+        let visible: Vec<usize> = layout.visible_columns().collect();
+        let mut last_visible = None;
+        let response = ScrollArea::horizontal()
+            .id_source(self.id)
+            .auto_shrink([false, false])
+            .show(ui, |ui| {
+                ui.vertical(|ui| {
+                    if self.pinned_header {
+                        self.header_row(ui, model, &mut layout, &visible, &mut widths, row_height);
+                        ui.separator();
+                    }
+                    ScrollArea::vertical()
+                        .id_source(self.id.with("body"))
+                        .auto_shrink([false, false])
+                        .max_height(ui.available_height() - footer_height)
+                        .show(ui, |ui| {
+                            if !self.pinned_header {
+                                self.header_row(
+                                    ui,
+                                    model,
+                                    &mut layout,
+                                    &visible,
+                                    &mut widths,
+                                    row_height,
+                                );
+                                ui.separator();
+                            }
+                            match &self.grouping {
+                                Some(grouping) => {
+                                    for (key, rows) in grouping.groups(model) {
+                                        let open = self.group_row(
+                                            ui,
+                                            model,
+                                            &key,
+                                            &rows,
+                                            &visible,
+                                            &mut widths,
+                                            row_height,
+                                        );
+                                        if open {
+                                            for row in rows {
+                                                let rect = data_row(
+                                                    ui,
+                                                    model,
+                                                    dependencies,
+                                                    row,
+                                                    &visible,
+                                                    &mut widths,
+                                                    row_height,
+                                                    editor,
+                                                );
+                                                if ui.clip_rect().intersects(rect) {
+                                                    last_visible = last_visible.max(Some(row));
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                                None => {
+                                    for row in 0..model.row_count() {
+                                        let rect = data_row(
+                                            ui,
+                                            model,
+                                            dependencies,
+                                            row,
+                                            &visible,
+                                            &mut widths,
+                                            row_height,
+                                            editor,
+                                        );
+                                        if ui.clip_rect().intersects(rect) {
+                                            last_visible = last_visible.max(Some(row));
+                                        }
+                                    }
+                                }
+                            }
+                        });
+                    if !self.aggregates.is_empty() {
+                        ui.separator();
+                        self.footer_row(ui, model, &visible, &mut widths, row_height);
+                    }
+                })
+                .response
+            })
+            .inner;
+
+        layout.store(ui.ctx(), self.id);
+        ui.data_mut(|data| data.insert_temp(widths_id, widths));
+        (response, last_visible)
+    }
+
+    fn header_row<T>(
+        &self,
+        ui: &mut Ui,
+        model: &T,
+        layout: &mut ColumnLayout,
+        visible: &[usize],
+        widths: &mut [f32],
+        row_height: f32,
+    ) where
+        T: TableModel,
+    {
+        ui.horizontal(|ui| {
+            for column in visible {
+                let header = model.column_header(*column);
+                let response = table_cell(
+                    ui,
+                    &mut widths[*column],
+                    row_height,
+                    model.column_kind(*column),
+                    |ui| {
+                        ui.add(
+                            Label::new(RichText::new(header.as_ref()).strong())
+                                .sense(Sense::click()),
+                        )
+                    },
+                );
+                if self.column_chooser {
+                    response.context_menu(|ui| self.column_chooser_ui(ui, model, layout, *column));
+                }
+            }
+        });
+    }
+
+    /// Show the header row of a group. Returns true if the group is expanded.
+    #[allow(clippy::too_many_arguments)]
+    fn group_row<T>(
+        &self,
+        ui: &mut Ui,
+        model: &T,
+        key: &str,
+        rows: &[usize],
+        visible: &[usize],
+        widths: &mut [f32],
+        row_height: f32,
+    ) -> bool
+    where
+        T: TableModel,
+    {
+        let collapsed_id = self.id.with("collapsed").with(key);
+        let mut collapsed: bool = ui.data(|data| data.get_temp(collapsed_id).unwrap_or_default());
+        ui.horizontal(|ui| {
+            for (position, column) in visible.iter().enumerate() {
+                table_cell(
+                    ui,
+                    &mut widths[*column],
+                    row_height,
+                    model.column_kind(*column),
+                    |ui| {
+                        if position == 0 {
+                            let icon = if collapsed { "⏵" } else { "⏷" };
+                            let key = if key.is_empty() { "(None)" } else { key };
+                            let text = format!("{} {} ({})", icon, key, rows.len());
+                            let response = ui.add(
+                                Label::new(RichText::new(text).strong()).sense(Sense::click()),
+                            );
+                            if response.clicked() {
+                                collapsed = !collapsed;
+                            }
+                            response
+                        } else if self.group_aggregates {
+                            let text = self
+                                .aggregates
+                                .iter()
+                                .filter(|(c, _)| c == column)
+                                .map(|(_, aggregate)| {
+                                    aggregate.display_rows(model, *column, rows.iter().copied())
+                                })
+                                .collect::<Vec<_>>()
+                                .join(", ");
+                            ui.label(RichText::new(text).weak())
+                        } else {
+                            ui.label("")
+                        }
+                    },
+                );
+            }
+        });
+        ui.data_mut(|data| data.insert_temp(collapsed_id, collapsed));
+        !collapsed
+    }
+
+    fn footer_row<T>(
+        &self,
+        ui: &mut Ui,
+        model: &T,
+        visible: &[usize],
+        widths: &mut [f32],
+        row_height: f32,
+    ) where
+        T: TableModel,
+    {
+        ui.horizontal(|ui| {
+            for column in visible {
+                table_cell(
+                    ui,
+                    &mut widths[*column],
+                    row_height,
+                    model.column_kind(*column),
+                    |ui| {
+                        let text = self
+                            .aggregates
+                            .iter()
+                            .filter(|(c, _)| c == column)
+                            .map(|(_, aggregate)| aggregate.display(model, *column))
+                            .collect::<Vec<_>>()
+                            .join(", ");
+                        ui.label(RichText::new(text).strong())
+                    },
+                );
+            }
+        });
+    }
+}
+
+#[derive(Default)]
+struct CellEditor {
+    enabled: bool,
+    /// The cell being edited and its text.
+    editing: Option<(usize, usize, String)>,
+    /// A finished edit to apply to the model.
+    commit: Option<(usize, usize, String)>,
+}
+
+#[allow(clippy::too_many_arguments)]
+fn data_row<T>(
+    ui: &mut Ui,
+    model: &T,
+    dependencies: &T::DecorationDependencies,
+    row: usize,
+    visible: &[usize],
+    widths: &mut [f32],
+    row_height: f32,
+    editor: &mut CellEditor,
+) -> Rect
+where
+    T: TableModel,
+{
+    ui.horizontal(|ui| {
+        for column in visible {
+            table_cell(
+                ui,
+                &mut widths[*column],
+                row_height,
+                model.column_kind(*column),
+                |ui| cell_ui(ui, model, dependencies, row, *column, editor),
+            );
+        }
+    })
+    .response
+    .rect
+}
+
+fn cell_ui<T>(
+    ui: &mut Ui,
+    model: &T,
+    dependencies: &T::DecorationDependencies,
+    row: usize,
+    column: usize,
+    editor: &mut CellEditor,
+) -> Response
+where
+    T: TableModel,
+{
+    if let Some((_, _, text)) = editor
+        .editing
+        .as_mut()
+        .filter(|(r, c, _)| *r == row && *c == column)
+    {
+        let response = ui.add(TextEdit::singleline(text).desired_width(f32::INFINITY));
+        if !response.has_focus() && !response.lost_focus() {
+            response.request_focus();
+        }
+        if response.lost_focus() {
+            if !ui.input(|i| i.key_pressed(Key::Escape)) {
+                editor.commit = Some((row, column, text.clone()));
+            }
+            editor.editing = None;
+        }
+        return response;
+    }
+    if model.column_decorated(column, DecorationKind::Table) {
+        model.with_cell_decoration(dependencies, row, column, DecorationKind::Table, |image| {
+            if let Some(image) = image {
+                ui.add(image);
+            }
+        });
+    }
+    let style = model.cell_style(row, column);
+    let sense = if editor.enabled {
+        Sense::click()
+    } else {
+        Sense::hover()
+    };
+    let response = model.with_cell_text(row, column, |text| {
+        ui.add(Label::new(style.apply(text, ui.visuals())).sense(sense))
+    });
+    let response = model.with_cell_tooltip(row, column, |tooltip| match tooltip {
+        Some(tooltip) => response.on_hover_text(tooltip),
+        None => response,
+    });
+    if !editor.enabled {
+        return response;
+    }
+    if response.double_clicked() {
+        editor.editing = Some((
+            row,
+            column,
+            model.with_cell_text(row, column, str::to_string),
+        ));
+    }
+    response
+}
+
+fn table_cell(
+    ui: &mut Ui,
+    width: &mut f32,
+    height: f32,
+    kind: ColumnKind,
+    add_contents: impl FnOnce(&mut Ui) -> Response,
+) -> Response {
+    let layout = match kind {
+        ColumnKind::Integer | ColumnKind::Float => Layout::right_to_left(Align::Center),
+        ColumnKind::Text | ColumnKind::Bool => Layout::left_to_right(Align::Center),
+    };
+    let inner = ui.allocate_ui_with_layout(vec2(*width, height), layout, |ui| {
+        ui.set_min_size(vec2(*width, height));
+        add_contents(ui)
+    });
+    *width = width.max(inner.response.rect.width());
+    inner.inner
+}
@@ -0,0 +1,274 @@
+use std::collections::VecDeque;
+
+use crate::{KeyedListModel, ListModel, ModelError};
+
+enum UndoOp<I> {
+    Insert { index: usize, item: I },
+    Remove { index: usize, item: I },
+    Swap(usize, usize),
+    Move { from: usize, to: usize },
+    Replace { index: usize, before: I, after: I },
+}
+
+/// Wraps a [ListModel] and records changes made through it so they can be undone and redone.
+///
+/// Structural changes made through the [ListModel] API are recorded automatically.
+/// Use [UndoableModel::edit] to record changes to an item. Changes made with [ListModel::item_mut] are NOT recorded.
+pub struct UndoableModel<M, I> {
+    model: M,
+    undo_stack: VecDeque<UndoOp<I>>,
+    redo_stack: Vec<UndoOp<I>>,
+    max_depth: usize,
+}
+
+impl<M, I> UndoableModel<M, I>
+where
+    M: ListModel<I>,
+    I: Clone,
+{
+    pub fn new(model: M) -> Self {
+        Self {
+            model,
+            undo_stack: VecDeque::new(),
+            redo_stack: vec![],
+            max_depth: 100,
+        }
+    }
+
+    /// Set the maximum number of changes that can be undone. Defaults to 100.
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        while self.undo_stack.len() > self.max_depth {
+            self.undo_stack.pop_front();
+        }
+        self
+    }
+
+    pub fn inner(&self) -> &M {
+        &self.model
+    }
+
+    pub fn into_inner(self) -> M {
+        self.model
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo_stack.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo_stack.is_empty()
+    }
+
+    /// Forget every recorded change.
+    pub fn clear_history(&mut self) {
+        self.undo_stack.clear();
+        self.redo_stack.clear();
+    }
+
+    /// Modify the item at the given index and record the change. Returns false if the index is out of bounds.
+    pub fn edit(&mut self, index: usize, edit: impl FnOnce(&mut I)) -> bool {
+        let Some(item) = self.model.item_mut(index) else {
+            return false;
+        };
+        let before = item.clone();
+        edit(item);
+        let after = item.clone();
+        self.record(UndoOp::Replace {
+            index,
+            before,
+            after,
+        });
+        true
+    }
+
+    /// Revert the most recent change. Returns false if there was nothing to undo.
+    pub fn undo(&mut self) -> bool {
+        let Some(op) = self.undo_stack.pop_back() else {
+            return false;
+        };
+        match &op {
+            UndoOp::Insert { index, .. } => self.model.remove(*index),
+            UndoOp::Remove { index, item } => self.model.insert(*index, item.clone()),
+            UndoOp::Swap(a, b) => self.model.swap_items(*a, *b),
+            UndoOp::Move { from, to } => self.model.move_item(*to, *from),
+            UndoOp::Replace { index, before, .. } => {
+                if let Some(item) = self.model.item_mut(*index) {
+                    *item = before.clone();
+                }
+            }
+        }
+        self.redo_stack.push(op);
+        true
+    }
+
+    /// Reapply the most recently undone change. Returns false if there was nothing to redo.
+    pub fn redo(&mut self) -> bool {
+        let Some(op) = self.redo_stack.pop() else {
+            return false;
+        };
+        match &op {
+            UndoOp::Insert { index, item } => self.model.insert(*index, item.clone()),
+            UndoOp::Remove { index, .. } => self.model.remove(*index),
+            UndoOp::Swap(a, b) => self.model.swap_items(*a, *b),
+            UndoOp::Move { from, to } => self.model.move_item(*from, *to),
+            UndoOp::Replace { index, after, .. } => {
+                if let Some(item) = self.model.item_mut(*index) {
+                    *item = after.clone();
+                }
+            }
+        }
+        self.undo_stack.push_back(op);
+        true
+    }
+
+    fn record(&mut self, op: UndoOp<I>) {
+        self.redo_stack.clear();
+        self.undo_stack.push_back(op);
+        while self.undo_stack.len() > self.max_depth {
+            self.undo_stack.pop_front();
+        }
+    }
+}
+
+impl<M, I> ListModel<I> for UndoableModel<M, I>
+where
+    M: ListModel<I>,
+    I: Clone,
+{
+    fn is_empty(&self) -> bool {
+        self.model.is_empty()
+    }
+
+    fn len(&self) -> usize {
+        self.model.len()
+    }
+
+    fn item(&self, index: usize) -> Option<&I> {
+        self.model.item(index)
+    }
+
+    fn item_mut(&mut self, index: usize) -> Option<&mut I> {
+        self.model.item_mut(index)
+    }
+
+    fn add(&mut self, item: I) {
+        let index = self.model.len();
+        self.model.add(item.clone());
+        if self.model.len() > index {
+            self.record(UndoOp::Insert { index, item });
+        }
+    }
+
+    fn insert(&mut self, index: usize, item: I) {
+        let len = self.model.len();
+        self.model.insert(index, item.clone());
+        if self.model.len() > len {
+            self.record(UndoOp::Insert { index, item });
+        }
+    }
+
+    fn remove(&mut self, index: usize) {
+        if let Some(item) = self.model.item(index).cloned() {
+            self.model.remove(index);
+            self.record(UndoOp::Remove { index, item });
+        }
+    }
+
+    fn swap_items(&mut self, a: usize, b: usize) {
+        if a < self.model.len() && b < self.model.len() {
+            self.model.swap_items(a, b);
+            self.record(UndoOp::Swap(a, b));
+        }
+    }
+
+    fn move_item(&mut self, from: usize, to: usize) {
+        if from < self.model.len() && to < self.model.len() && from != to {
+            self.model.move_item(from, to);
+            self.record(UndoOp::Move { from, to });
+        }
+    }
+
+    fn copy(&mut self, a: usize, b: usize) {
+        if a >= self.model.len() {
+            return;
+        }
+        if let Some(before) = self.model.item(b).cloned() {
+            self.model.copy(a, b);
+            if let Some(after) = self.model.item(b).cloned() {
+                self.record(UndoOp::Replace {
+                    index: b,
+                    before,
+                    after,
+                });
+            }
+        }
+    }
+
+    fn row_to_index(&self, row_number: usize) -> Option<usize> {
+        self.model.row_to_index(row_number)
+    }
+
+    fn generation(&self) -> Option<u64> {
+        self.model.generation()
+    }
+
+    fn try_add(&mut self, item: I) -> Result<(), ModelError> {
+        let index = self.model.len();
+        self.model.try_add(item.clone())?;
+        self.record(UndoOp::Insert { index, item });
+        Ok(())
+    }
+
+    fn try_insert(&mut self, index: usize, item: I) -> Result<(), ModelError> {
+        self.model.try_insert(index, item.clone())?;
+        self.record(UndoOp::Insert { index, item });
+        Ok(())
+    }
+
+    fn try_remove(&mut self, index: usize) -> Result<(), ModelError> {
+        let item = self.model.item(index).cloned();
+        self.model.try_remove(index)?;
+        if let Some(item) = item {
+            self.record(UndoOp::Remove { index, item });
+        }
+        Ok(())
+    }
+
+    fn try_swap_items(&mut self, a: usize, b: usize) -> Result<(), ModelError> {
+        self.model.try_swap_items(a, b)?;
+        self.record(UndoOp::Swap(a, b));
+        Ok(())
+    }
+
+    fn try_move_item(&mut self, from: usize, to: usize) -> Result<(), ModelError> {
+        self.model.try_move_item(from, to)?;
+        if from != to {
+            self.record(UndoOp::Move { from, to });
+        }
+        Ok(())
+    }
+
+    fn try_copy(&mut self, a: usize, b: usize) -> Result<(), ModelError> {
+        let before = self.model.item(b).cloned();
+        self.model.try_copy(a, b)?;
+        if let (Some(before), Some(after)) = (before, self.model.item(b).cloned()) {
+            self.record(UndoOp::Replace {
+                index: b,
+                before,
+                after,
+            });
+        }
+        Ok(())
+    }
+}
+
+impl<M, I> KeyedListModel<I> for UndoableModel<M, I>
+where
+    M: KeyedListModel<I>,
+    I: Clone,
+{
+    fn index_of(&self, key: &str) -> Option<usize> {
+        self.model.index_of(key)
+    }
+}
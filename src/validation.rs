@@ -0,0 +1,100 @@
+use std::fmt::{Display, Formatter};
+
+use egui::{Color32, Response, RichText, Ui, Visuals};
+
+use crate::ListModel;
+
+/// How serious a [ValidationError] is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    /// The item is usable but probably not what the user intended.
+    Warning,
+    /// The item will not work, ex. a reference to a missing record.
+    Error,
+}
+
+impl Severity {
+    pub fn color(&self, visuals: &Visuals) -> Color32 {
+        match self {
+            Severity::Warning => visuals.warn_fg_color,
+            Severity::Error => visuals.error_fg_color,
+        }
+    }
+}
+
+/// A problem with an item reported by [ValidatedItem::validate].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationError {
+    pub severity: Severity,
+    pub message: String,
+}
+
+impl ValidationError {
+    pub fn warning(message: impl Into<String>) -> Self {
+        Self {
+            severity: Severity::Warning,
+            message: message.into(),
+        }
+    }
+
+    pub fn error(message: impl Into<String>) -> Self {
+        Self {
+            severity: Severity::Error,
+            message: message.into(),
+        }
+    }
+}
+
+impl Display for ValidationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+/// An item which can check itself for problems.
+/// Widgets mark invalid rows when given the errors (see [crate::RowLayout::validation] and [crate::validated_item_list]).
+pub trait ValidatedItem {
+    /// Every problem with the item. An empty list means the item is valid.
+    fn validate(&self) -> Vec<ValidationError>;
+}
+
+/// Validation summaries for models of [ValidatedItem]s, ex. an error count for a toolbar.
+pub trait ValidatedModel<I>: ListModel<I> {
+    /// The problems with the item at the index.
+    fn errors(&self, index: usize) -> Vec<ValidationError>;
+
+    /// The indices of every item with at least one problem.
+    fn invalid_rows(&self) -> Vec<usize> {
+        (0..self.len())
+            .filter(|index| !self.errors(*index).is_empty())
+            .collect()
+    }
+
+    /// The total number of problems across every item.
+    fn error_count(&self) -> usize {
+        (0..self.len()).map(|index| self.errors(index).len()).sum()
+    }
+}
+
+impl<M, I> ValidatedModel<I> for M
+where
+    M: ListModel<I>,
+    I: ValidatedItem,
+{
+    fn errors(&self, index: usize) -> Vec<ValidationError> {
+        self.item(index)
+            .map(ValidatedItem::validate)
+            .unwrap_or_default()
+    }
+}
+
+/// A warning sign colored by the most severe error. Hover to see every message.
+pub(crate) fn validation_badge(ui: &mut Ui, errors: &[ValidationError]) -> Option<Response> {
+    let severity = errors.iter().map(|error| error.severity).max()?;
+    let color = severity.color(ui.visuals());
+    let messages: Vec<String> = errors.iter().map(ToString::to_string).collect();
+    Some(
+        ui.label(RichText::new("⚠").color(color))
+            .on_hover_text(messages.join("\n")),
+    )
+}
@@ -0,0 +1,274 @@
+use std::cell::RefCell;
+use std::collections::{BTreeSet, HashMap};
+use std::ops::{Range, RangeInclusive};
+use std::sync::mpsc::Receiver;
+
+use crate::{ListModel, ModelError};
+
+pub(crate) type FetchFn<I> = Box<dyn FnMut(Range<usize>) -> Vec<I>>;
+pub(crate) type RequestFn = Box<dyn FnMut(usize, Range<usize>)>;
+
+pub(crate) enum PageSource<I> {
+    Sync(FetchFn<I>),
+    Channel {
+        request: RequestFn,
+        receiver: Receiver<(usize, Vec<I>)>,
+    },
+}
+
+/// A [ListModel] over a dataset that is too large to hold in memory.
+/// The total length is known up front and items are loaded a page at a time.
+///
+/// Reading an item which isn't loaded yet returns [None] and queues its page.
+/// Call [VirtualListModel::update] once per frame to load queued pages.
+///
+/// The length is controlled by the backend, so the model's structure is read only:
+/// [ListModel::add], [ListModel::insert], and [ListModel::remove] do nothing and their `try_*`
+/// counterparts return [ModelError::Unsupported]. Use [VirtualListModel::set_len] when the backend's data changes.
+/// Items in loaded pages can still be edited, swapped, moved, and copied. Doing so with an item which
+/// isn't loaded does nothing, and the `try_*` methods return [ModelError::Rejected].
+pub struct VirtualListModel<I> {
+    len: usize,
+    page_size: usize,
+    pages: HashMap<usize, Vec<I>>,
+    queued: RefCell<BTreeSet<usize>>,
+    pending: BTreeSet<usize>,
+    source: PageSource<I>,
+}
+
+impl<I> VirtualListModel<I> {
+    /// Create a model which loads pages synchronously with the given callback.
+    /// The callback receives the range of indices to load.
+    pub fn new(
+        len: usize,
+        page_size: usize,
+        fetch: impl FnMut(Range<usize>) -> Vec<I> + 'static,
+    ) -> Self {
+        Self::with_source(len, page_size, PageSource::Sync(Box::new(fetch)))
+    }
+
+    /// Create a model which loads pages in the background. `request` receives the page number and
+    /// range of indices to load, and the loaded page should be sent back through `receiver`.
+    pub fn with_channel(
+        len: usize,
+        page_size: usize,
+        request: impl FnMut(usize, Range<usize>) + 'static,
+        receiver: Receiver<(usize, Vec<I>)>,
+    ) -> Self {
+        Self::with_source(
+            len,
+            page_size,
+            PageSource::Channel {
+                request: Box::new(request),
+                receiver,
+            },
+        )
+    }
+
+    fn with_source(len: usize, page_size: usize, source: PageSource<I>) -> Self {
+        Self {
+            len,
+            page_size: page_size.max(1),
+            pages: HashMap::new(),
+            queued: RefCell::new(BTreeSet::new()),
+            pending: BTreeSet::new(),
+            source,
+        }
+    }
+
+    pub fn page_size(&self) -> usize {
+        self.page_size
+    }
+
+    /// Change the total length, ex. after the backend reports new data. Loaded pages are discarded.
+    pub fn set_len(&mut self, len: usize) {
+        self.len = len;
+        self.invalidate();
+    }
+
+    /// Discard every loaded page so they will be fetched again.
+    pub fn invalidate(&mut self) {
+        self.pages.clear();
+        self.pending.clear();
+        self.queued.borrow_mut().clear();
+    }
+
+    /// Whether the item at the given index has been loaded.
+    pub fn is_loaded(&self, index: usize) -> bool {
+        self.pages.contains_key(&(index / self.page_size))
+    }
+
+    /// Queue the pages covering the given range, ex. rows that are about to scroll into view.
+    pub fn prefetch(&self, range: Range<usize>) {
+        let end = range.end.min(self.len);
+        if range.start >= end {
+            return;
+        }
+        let mut queued = self.queued.borrow_mut();
+        for page in range.start / self.page_size..=(end - 1) / self.page_size {
+            if !self.pages.contains_key(&page) {
+                queued.insert(page);
+            }
+        }
+    }
+
+    /// Fetch queued pages and receive pages loaded in the background. Returns true if any page was loaded.
+    pub fn update(&mut self) -> bool {
+        let queued = std::mem::take(self.queued.get_mut());
+        let mut loaded = false;
+        for page in queued {
+            if self.pages.contains_key(&page) || self.pending.contains(&page) {
+                continue;
+            }
+            let range = page * self.page_size..((page + 1) * self.page_size).min(self.len);
+            match &mut self.source {
+                PageSource::Sync(fetch) => {
+                    let items = fetch(range);
+                    self.pages.insert(page, items);
+                    loaded = true;
+                }
+                PageSource::Channel { request, .. } => {
+                    request(page, range);
+                    self.pending.insert(page);
+                }
+            }
+        }
+        if let PageSource::Channel { receiver, .. } = &self.source {
+            while let Ok((page, items)) = receiver.try_recv() {
+                // Ignore pages requested before the model was invalidated.
+                if self.pending.remove(&page) {
+                    self.pages.insert(page, items);
+                    loaded = true;
+                }
+            }
+        }
+        loaded
+    }
+
+    fn locate(&self, index: usize) -> (usize, usize) {
+        (index / self.page_size, index % self.page_size)
+    }
+
+    /// Check that every item in the range is loaded, queueing the pages which aren't.
+    fn check_loaded(&self, indices: RangeInclusive<usize>) -> Result<(), ModelError> {
+        if *indices.end() >= self.len {
+            return Err(ModelError::OutOfBounds {
+                index: *indices.end(),
+                len: self.len,
+            });
+        }
+        self.prefetch(*indices.start()..*indices.end() + 1);
+        let (first, _) = self.locate(*indices.start());
+        let (last, _) = self.locate(*indices.end());
+        if (first..=last).all(|page| self.pages.contains_key(&page)) {
+            Ok(())
+        } else {
+            Err(ModelError::Rejected)
+        }
+    }
+}
+
+impl<I> ListModel<I> for VirtualListModel<I>
+where
+    I: Clone,
+{
+    fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn item(&self, index: usize) -> Option<&I> {
+        if index >= self.len {
+            return None;
+        }
+        let (page, offset) = self.locate(index);
+        match self.pages.get(&page) {
+            Some(items) => items.get(offset),
+            None => {
+                self.queued.borrow_mut().insert(page);
+                None
+            }
+        }
+    }
+
+    fn item_mut(&mut self, index: usize) -> Option<&mut I> {
+        if index >= self.len {
+            return None;
+        }
+        let (page, offset) = self.locate(index);
+        match self.pages.get_mut(&page) {
+            Some(items) => items.get_mut(offset),
+            None => {
+                self.queued.get_mut().insert(page);
+                None
+            }
+        }
+    }
+
+    fn add(&mut self, _: I) {}
+
+    fn insert(&mut self, _: usize, _: I) {}
+
+    fn remove(&mut self, _: usize) {}
+
+    fn swap_items(&mut self, a: usize, b: usize) {
+        if let (Some(item_a), Some(item_b)) = (self.item(a).cloned(), self.item(b).cloned()) {
+            if let Some(item) = self.item_mut(a) {
+                *item = item_b;
+            }
+            if let Some(item) = self.item_mut(b) {
+                *item = item_a;
+            }
+        }
+    }
+
+    fn copy(&mut self, a: usize, b: usize) {
+        if let Some(source) = self.item(a).cloned() {
+            if let Some(item) = self.item_mut(b) {
+                *item = source;
+            }
+        }
+    }
+
+    fn row_to_index(&self, row_number: usize) -> Option<usize> {
+        (row_number < self.len).then_some(row_number)
+    }
+
+    fn try_add(&mut self, _: I) -> Result<(), ModelError> {
+        Err(READ_ONLY)
+    }
+
+    fn try_insert(&mut self, _: usize, _: I) -> Result<(), ModelError> {
+        Err(READ_ONLY)
+    }
+
+    fn try_remove(&mut self, _: usize) -> Result<(), ModelError> {
+        Err(READ_ONLY)
+    }
+
+    fn try_swap_items(&mut self, a: usize, b: usize) -> Result<(), ModelError> {
+        // Check both items so every missing page is queued.
+        self.check_loaded(a..=a).and(self.check_loaded(b..=b))?;
+        self.swap_items(a, b);
+        Ok(())
+    }
+
+    fn try_move_item(&mut self, from: usize, to: usize) -> Result<(), ModelError> {
+        self.check_loaded(from.min(to)..=from.max(to))?;
+        self.move_item(from, to);
+        Ok(())
+    }
+
+    fn try_copy(&mut self, a: usize, b: usize) -> Result<(), ModelError> {
+        // Check both items so every missing page is queued.
+        self.check_loaded(a..=a).and(self.check_loaded(b..=b))?;
+        self.copy(a, b);
+        Ok(())
+    }
+}
+
+const READ_ONLY: ModelError =
+    ModelError::Unsupported("changing the length of a VirtualListModel, use set_len instead");